@@ -0,0 +1,62 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rust_strings::{extract_into, ExtractedString, FileConfig, StringWriter};
+use std::error::Error;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+const FILE_SIZE: usize = 64 * 1024 * 1024;
+
+/// Counts the strings instead of collecting them, so that the benchmark measures the scan.
+struct CountWriter(usize);
+
+impl StringWriter for CountWriter {
+    fn write_string(&mut self, _: ExtractedString) -> Result<(), Box<dyn Error>> {
+        self.0 += 1;
+        Ok(())
+    }
+}
+
+/// A file of mostly binary data with a short string every few hundred bytes.
+fn create_file() -> NamedTempFile {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut bytes = Vec::with_capacity(FILE_SIZE);
+    while bytes.len() < FILE_SIZE {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        if state & 0xff == 0 {
+            bytes.extend_from_slice(b"libc.so.6\x00");
+        } else {
+            bytes.push((state >> 56) as u8 & 0x8f);
+        }
+    }
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(&bytes).unwrap();
+    file
+}
+
+fn bench_mmap(c: &mut Criterion) {
+    let file = create_file();
+    let mut group = c.benchmark_group("file");
+    group.throughput(Throughput::Bytes(FILE_SIZE as u64));
+    group.sample_size(10);
+    for threads in [1, 4] {
+        for mmap in [false, true] {
+            let name = if mmap { "mmap" } else { "buffered" };
+            group.bench_with_input(BenchmarkId::new(name, threads), &threads, |b, &threads| {
+                let config = FileConfig::new(file.path())
+                    .with_mmap(mmap)
+                    .with_threads(threads);
+                b.iter(|| {
+                    let mut writer = CountWriter(0);
+                    extract_into(&config, &mut writer).unwrap();
+                    writer.0
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_mmap);
+criterion_main!(benches);
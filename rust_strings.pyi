@@ -2,23 +2,113 @@ from pathlib import Path
 from typing import Optional, List, Tuple, Union
 
 
+class ExtractedString:
+    """
+    A string found in the input, along with its location and encoding. Unlike the Rust struct, it has
+    no file_name, member or compression, as the functions scan a single file or bytes as is.
+    """
+
+    text: str
+    offset: int
+    end_offset: int
+    byte_length: int
+    encoding: str
+    null_terminated: Optional[bool]
+    section: Optional[str]
+    virtual_address: Optional[int]
+    relative_virtual_address: Optional[int]
+    resource: Optional[str]
+    architecture: Optional[str]
+    cfstring_address: Optional[int]
+    mapping: Optional[str]
+
+
 def strings(
     file_path: Optional[Union[str, Path]] = None,
     bytes: Optional[bytes] = None,
+    *,
     min_length: int = 3,
+    max_length: Optional[int] = None,
+    max_length_policy: str = "truncate",
+    null_terminated_only: bool = False,
     encodings: List[str] = ["ascii"],
     buffer_size: int = 1024 * 1024,
+    unicode_scripts: List[str] = [],
+    mmap: bool = False,
+    threads: int = 1,
+    start_offset: int = 0,
+    end_offset: Optional[int] = None,
+    binary_format: str = "raw",
+    sections: List[str] = [],
 ) -> List[Tuple[str, int]]:
     """
     Extract strings from binary file or bytes.
     :param file_path: path to file (can't be with bytes option)
     :param bytes: bytes (can't be with file_path option)
     :param min_length: strings minimum length
+    :param max_length: strings maximum length, longer strings are handled according to max_length_policy (default is no maximum)
+    :param max_length_policy: what happens to strings longer than max_length, "drop", "truncate" to cut them and end them with "..." or "split" into consecutive chunks (default is "truncate")
+    :param null_terminated_only: extract only the strings ended by a null character of their encoding, e.g. 0x00 in ascii or 0x0000 in utf-16, whose null_terminated is True (default is False)
     :param encodings: strings encodings (default is ["ascii"])
     :param buffer_size: the buffer size to read the file (relevant only to file_path option)
+    :param unicode_scripts: unicode scripts allowed in utf-16 strings besides ascii (default is [])
+    :param mmap: map the file into memory instead of reading it with a buffer, only faster with several threads (relevant only to file_path option)
+    :param threads: number of threads scanning the input, 0 uses every core (default is 1)
+    :param start_offset: offset to start scanning from, offsets stay relative to the whole input
+    :param end_offset: offset to stop scanning at (default is the end of the input)
+    :param binary_format: parse the input as an executable and scan its sections only, "raw", "elf", "pe", "macho" or "core" for ELF core dumps, whose sections are the paths of mapped files (default is "raw")
+    :param sections: sections to scan when parsing an executable, e.g. [".rodata"] or ["__TEXT,__cstring"] (default is every section)
     :return: list of tuples of string and offset
     :raises: raise StringsException if there is any error during string extraction
              raise EncodingNotFoundException if the function got an unsupported encondings
+             raise UnicodeScriptNotFoundException if the function got an unsupported unicode scripts
+             raise BinaryFormatNotFoundException if the function got an unsupported binary format
+             raise MaxLengthPolicyNotFoundException if the function got an unsupported max length policy
+    """
+    ...
+
+
+def extract_strings(
+    file_path: Optional[Union[str, Path]] = None,
+    bytes: Optional[bytes] = None,
+    *,
+    min_length: int = 3,
+    max_length: Optional[int] = None,
+    max_length_policy: str = "truncate",
+    null_terminated_only: bool = False,
+    encodings: List[str] = ["ascii"],
+    buffer_size: int = 1024 * 1024,
+    unicode_scripts: List[str] = [],
+    mmap: bool = False,
+    threads: int = 1,
+    start_offset: int = 0,
+    end_offset: Optional[int] = None,
+    binary_format: str = "raw",
+    sections: List[str] = [],
+) -> List[ExtractedString]:
+    """
+    Extract strings from binary file or bytes, along with their location and encoding.
+    :param file_path: path to file (can't be with bytes option)
+    :param bytes: bytes (can't be with file_path option)
+    :param min_length: strings minimum length
+    :param max_length: strings maximum length, longer strings are handled according to max_length_policy (default is no maximum)
+    :param max_length_policy: what happens to strings longer than max_length, "drop", "truncate" to cut them and end them with "..." or "split" into consecutive chunks (default is "truncate")
+    :param null_terminated_only: extract only the strings ended by a null character of their encoding, e.g. 0x00 in ascii or 0x0000 in utf-16, whose null_terminated is True (default is False)
+    :param encodings: strings encodings (default is ["ascii"])
+    :param buffer_size: the buffer size to read the file (relevant only to file_path option)
+    :param unicode_scripts: unicode scripts allowed in utf-16 strings besides ascii (default is [])
+    :param mmap: map the file into memory instead of reading it with a buffer, only faster with several threads (relevant only to file_path option)
+    :param threads: number of threads scanning the input, 0 uses every core (default is 1)
+    :param start_offset: offset to start scanning from, offsets stay relative to the whole input
+    :param end_offset: offset to stop scanning at (default is the end of the input)
+    :param binary_format: parse the input as an executable and scan its sections only, "raw", "elf", "pe", "macho" or "core" for ELF core dumps, whose sections are the paths of mapped files (default is "raw")
+    :param sections: sections to scan when parsing an executable, e.g. [".rodata"] or ["__TEXT,__cstring"] (default is every section)
+    :return: list of ExtractedString
+    :raises: raise StringsException if there is any error during string extraction
+             raise EncodingNotFoundException if the function got an unsupported encondings
+             raise UnicodeScriptNotFoundException if the function got an unsupported unicode scripts
+             raise BinaryFormatNotFoundException if the function got an unsupported binary format
+             raise MaxLengthPolicyNotFoundException if the function got an unsupported max length policy
     """
     ...
 
@@ -27,9 +117,20 @@ def dump_strings(
     output_file: Union[str, Path],
     file_path: Optional[Union[str, Path]] = None,
     bytes: Optional[bytes] = None,
+    *,
     min_length: int = 3,
+    max_length: Optional[int] = None,
+    max_length_policy: str = "truncate",
+    null_terminated_only: bool = False,
     encodings: List[str] = ["ascii"],
     buffer_size: int = 1024 * 1024,
+    unicode_scripts: List[str] = [],
+    mmap: bool = False,
+    threads: int = 1,
+    start_offset: int = 0,
+    end_offset: Optional[int] = None,
+    binary_format: str = "raw",
+    sections: List[str] = [],
 ) -> List[Tuple[str, int]]:
     """
     Dump strings from binary file or bytes to json file.
@@ -37,10 +138,23 @@ def dump_strings(
     :param file_path: path to file (can't be with bytes option)
     :param bytes: bytes (can't be with file_path option)
     :param min_length: strings minimum length
+    :param max_length: strings maximum length, longer strings are handled according to max_length_policy (default is no maximum)
+    :param max_length_policy: what happens to strings longer than max_length, "drop", "truncate" to cut them and end them with "..." or "split" into consecutive chunks (default is "truncate")
+    :param null_terminated_only: extract only the strings ended by a null character of their encoding, e.g. 0x00 in ascii or 0x0000 in utf-16, whose null_terminated is True (default is False)
     :param encodings: strings encodings (default is ["ascii"])
     :param buffer_size: the buffer size to read the file (relevant only to file_path option)
+    :param unicode_scripts: unicode scripts allowed in utf-16 strings besides ascii (default is [])
+    :param mmap: map the file into memory instead of reading it with a buffer, only faster with several threads (relevant only to file_path option)
+    :param threads: number of threads scanning the input, 0 uses every core (default is 1)
+    :param start_offset: offset to start scanning from, offsets stay relative to the whole input
+    :param end_offset: offset to stop scanning at (default is the end of the input)
+    :param binary_format: parse the input as an executable and scan its sections only, "raw", "elf", "pe", "macho" or "core" for ELF core dumps, whose sections are the paths of mapped files (default is "raw")
+    :param sections: sections to scan when parsing an executable, e.g. [".rodata"] or ["__TEXT,__cstring"] (default is every section)
     :return: list of tuples of string and offset
     :raises: raise StringsException if there is any error during string extraction
              raise EncodingNotFoundException if the function got an unsupported encondings
+             raise UnicodeScriptNotFoundException if the function got an unsupported unicode scripts
+             raise BinaryFormatNotFoundException if the function got an unsupported binary format
+             raise MaxLengthPolicyNotFoundException if the function got an unsupported max length policy
     """
     ...
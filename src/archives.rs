@@ -0,0 +1,244 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Read, Seek, SeekFrom};
+
+#[cfg(feature = "gzip")]
+use flate2::read::DeflateDecoder;
+use zip::{CompressionMethod, ZipArchive};
+
+use crate::strings::Config;
+
+pub(crate) const DEFAULT_MAX_ARCHIVE_DEPTH: usize = 4;
+pub(crate) const DEFAULT_MAX_MEMBER_SIZE: u64 = 256 * 1024 * 1024;
+
+/// A member of an archive is bigger than the configured size limit.
+#[derive(Debug)]
+pub struct MemberTooLargeError {
+    max_member_size: u64,
+}
+
+impl fmt::Display for MemberTooLargeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Member is larger than the limit of {} bytes",
+            self.max_member_size
+        )
+    }
+}
+
+impl MemberTooLargeError {
+    fn new(max_member_size: u64) -> Self {
+        MemberTooLargeError { max_member_size }
+    }
+}
+
+impl Error for MemberTooLargeError {}
+
+/// A file inside an archive, scanned as an input of its own.
+#[doc(hidden)]
+#[derive(Debug, Clone)]
+pub struct Member {
+    path: String,
+    location: MemberLocation,
+    max_size: u64,
+}
+
+#[derive(Debug, Clone)]
+enum MemberLocation {
+    Zip {
+        data_start: u64,
+        compressed_size: u64,
+        compression: CompressionMethod,
+        encrypted: bool,
+    },
+    Tar {
+        data_start: u64,
+        size: u64,
+    },
+}
+
+impl Member {
+    /// Path of the member within its archive.
+    pub(crate) fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+/// Members of the input of the config when it is a zip or tar archive, `None` otherwise.
+pub(crate) fn find_members<T: Config>(
+    strings_config: &T,
+    max_member_size: u64,
+) -> Result<Option<Vec<Member>>, Box<dyn Error>> {
+    let mut header = vec![];
+    strings_config
+        .get_range_reader(0, Some(512))?
+        .read_to_end(&mut header)?;
+    if header.starts_with(b"PK\x03\x04") || header.starts_with(b"PK\x05\x06") {
+        return Ok(Some(find_zip_members(strings_config, max_member_size)?));
+    }
+    if header.get(257..262) == Some(b"ustar") {
+        return Ok(Some(find_tar_members(strings_config, max_member_size)?));
+    }
+    Ok(None)
+}
+
+fn find_zip_members<T: Config>(
+    strings_config: &T,
+    max_member_size: u64,
+) -> Result<Vec<Member>, Box<dyn Error>> {
+    let mut archive = ZipArchive::new(InputSeeker::new(strings_config))?;
+    let mut members = vec![];
+    for index in 0..archive.len() {
+        let file = archive.by_index_raw(index)?;
+        if !file.is_file() {
+            continue;
+        }
+        let data_start = match file.data_start() {
+            Some(data_start) => data_start,
+            None => continue,
+        };
+        members.push(Member {
+            path: file.name().to_owned(),
+            location: MemberLocation::Zip {
+                data_start,
+                compressed_size: file.compressed_size(),
+                compression: file.compression(),
+                encrypted: file.encrypted(),
+            },
+            max_size: max_member_size,
+        });
+    }
+    Ok(members)
+}
+
+fn find_tar_members<T: Config>(
+    strings_config: &T,
+    max_member_size: u64,
+) -> Result<Vec<Member>, Box<dyn Error>> {
+    let mut archive = tar::Archive::new(InputSeeker::new(strings_config));
+    let mut members = vec![];
+    for entry in archive.entries_with_seek()? {
+        let entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        members.push(Member {
+            path: entry.path()?.to_string_lossy().into_owned(),
+            location: MemberLocation::Tar {
+                data_start: entry.raw_file_position(),
+                size: entry.size(),
+            },
+            max_size: max_member_size,
+        });
+    }
+    Ok(members)
+}
+
+/// Reads the decompressed contents of a member, up to its size limit.
+pub(crate) fn read_member<T: Config>(
+    strings_config: &T,
+    member: &Member,
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    let reader: Box<dyn Read + '_> = match member.location {
+        MemberLocation::Zip {
+            encrypted: true, ..
+        } => return Err("Encrypted members can't be read".into()),
+        MemberLocation::Zip {
+            data_start,
+            compressed_size,
+            compression,
+            ..
+        } => {
+            let reader = strings_config.get_range_reader(data_start, Some(compressed_size))?;
+            match compression {
+                CompressionMethod::Stored => reader,
+                // Deflate is the codec of gzip, decompressed by the same feature
+                #[cfg(feature = "gzip")]
+                CompressionMethod::Deflated => Box::new(DeflateDecoder::new(reader)),
+                compression => {
+                    return Err(format!("Unsupported compression method {}", compression).into())
+                }
+            }
+        }
+        MemberLocation::Tar { data_start, size } => {
+            strings_config.get_range_reader(data_start, Some(size))?
+        }
+    };
+    let mut bytes = vec![];
+    reader
+        .take(member.max_size.saturating_add(1))
+        .read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > member.max_size {
+        return Err(Box::new(MemberTooLargeError::new(member.max_size)));
+    }
+    Ok(bytes)
+}
+
+/// Seekable reader over the input of a config, which reopens the reader of the config after a
+/// seek.
+pub(crate) struct InputSeeker<'a, T> {
+    strings_config: &'a T,
+    position: u64,
+    length: Option<u64>,
+    reader: Option<Box<dyn BufRead + 'a>>,
+}
+
+impl<'a, T: Config> InputSeeker<'a, T> {
+    pub(crate) fn new(strings_config: &'a T) -> Self {
+        InputSeeker {
+            strings_config,
+            position: 0,
+            length: None,
+            reader: None,
+        }
+    }
+
+    fn get_length(&mut self) -> io::Result<u64> {
+        if let Some(length) = self.length {
+            return Ok(length);
+        }
+        let mut reader = self
+            .strings_config
+            .get_range_reader(0, None)
+            .map_err(|err| io::Error::other(err.to_string()))?;
+        let length = io::copy(&mut reader, &mut io::sink())?;
+        self.length = Some(length);
+        Ok(length)
+    }
+}
+
+impl<T: Config> Read for InputSeeker<'_, T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let reader = match self.reader.as_mut() {
+            Some(reader) => reader,
+            None => {
+                let reader = self
+                    .strings_config
+                    .get_range_reader(self.position, None)
+                    .map_err(|err| io::Error::other(err.to_string()))?;
+                self.reader.insert(reader)
+            }
+        };
+        let length = reader.read(buf)?;
+        self.position += length as u64;
+        Ok(length)
+    }
+}
+
+impl<T: Config> Seek for InputSeeker<'_, T> {
+    fn seek(&mut self, position: SeekFrom) -> io::Result<u64> {
+        let position = match position {
+            SeekFrom::Start(position) => Some(position),
+            SeekFrom::End(delta) => self.get_length()?.checked_add_signed(delta),
+            SeekFrom::Current(delta) => self.position.checked_add_signed(delta),
+        };
+        let position = position
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Seek before the start"))?;
+        if position != self.position {
+            self.position = position;
+            self.reader = None;
+        }
+        Ok(position)
+    }
+}
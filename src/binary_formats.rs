@@ -0,0 +1,363 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use object::read::macho::{MachOFatFile32, MachOFatFile64};
+use object::{elf, Endianness, FileKind, Object, ObjectSection, ReadRef, SectionFlags};
+
+use crate::core_dump::find_core_regions;
+use crate::encodings::Encoding;
+use crate::extracted_string::ExtractedString;
+use crate::macho::{find_architecture, find_cfstrings, find_fat_regions};
+use crate::pe_resources::find_resources;
+
+/// Format the input is parsed as before extracting strings.
+///
+/// Executable formats scan the contents of the sections only, each section on its own, and
+/// attribute every string to its section and virtual address.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum BinaryFormat {
+    /// The input is scanned as a flat blob
+    #[default]
+    Raw,
+    Elf,
+    /// Portable executable, resource string tables and version info are parsed into their strings
+    Pe,
+    /// Mach-O or fat binary, whose strings are attributed to their architecture and CFStrings are
+    /// resolved to their text
+    MachO,
+    /// ELF core dump, whose dumped `PT_LOAD` segments are scanned and attributed to their virtual
+    /// address and to the file mapped there according to the `NT_FILE` note, zero-filled ones are
+    /// skipped. The sections to scan are the paths of mapped files
+    Core,
+}
+
+impl fmt::Display for BinaryFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug)]
+pub struct BinaryFormatNotFoundError {
+    binary_format: String,
+}
+
+impl fmt::Display for BinaryFormatNotFoundError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Binary format not found: {:?}", self.binary_format)
+    }
+}
+
+impl BinaryFormatNotFoundError {
+    fn new(binary_format: String) -> Self {
+        BinaryFormatNotFoundError { binary_format }
+    }
+}
+
+impl Error for BinaryFormatNotFoundError {}
+
+impl FromStr for BinaryFormat {
+    type Err = BinaryFormatNotFoundError;
+
+    fn from_str(binary_format: &str) -> Result<Self, Self::Err> {
+        let binary_format: &str = &binary_format.to_lowercase();
+        match binary_format {
+            "raw" => Ok(BinaryFormat::Raw),
+            "elf" => Ok(BinaryFormat::Elf),
+            "pe" => Ok(BinaryFormat::Pe),
+            "macho" | "mach-o" => Ok(BinaryFormat::MachO),
+            "core" => Ok(BinaryFormat::Core),
+            _ => Err(BinaryFormatNotFoundError::new(binary_format.to_owned())),
+        }
+    }
+}
+
+/// The input doesn't match the requested [`BinaryFormat`].
+#[derive(Debug)]
+pub struct InvalidBinaryFormatError {
+    binary_format: BinaryFormat,
+}
+
+impl fmt::Display for InvalidBinaryFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Input is not a valid {} file", self.binary_format)
+    }
+}
+
+impl InvalidBinaryFormatError {
+    pub(crate) fn new(binary_format: BinaryFormat) -> Self {
+        InvalidBinaryFormatError { binary_format }
+    }
+}
+
+impl Error for InvalidBinaryFormatError {}
+
+/// Part of the input scanned on its own, the strings found in it are attributed to it.
+#[doc(hidden)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub offset: u64,
+    pub length: u64,
+    pub section: Option<String>,
+    /// Virtual address of the first byte of the region
+    pub virtual_address: Option<u64>,
+    pub relative_virtual_address: Option<u64>,
+    pub resource: Option<String>,
+    pub architecture: Option<String>,
+    pub cfstring_address: Option<u64>,
+    pub mapping: Option<String>,
+    /// String read from the structure of the input, the region isn't scanned then
+    pub parsed_string: Option<ParsedString>,
+    /// Whether the region is skipped when all of its bytes are zero, e.g. zero-filled memory
+    pub skip_if_zero: bool,
+}
+
+#[doc(hidden)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedString {
+    pub text: String,
+    pub encoding: Encoding,
+    pub null_terminated: Option<bool>,
+}
+
+impl Region {
+    pub(crate) fn new(offset: u64, length: u64) -> Self {
+        Region {
+            offset,
+            length,
+            section: None,
+            virtual_address: None,
+            relative_virtual_address: None,
+            resource: None,
+            architecture: None,
+            cfstring_address: None,
+            mapping: None,
+            parsed_string: None,
+            skip_if_zero: false,
+        }
+    }
+
+    /// Part of the region from `offset`, with the addresses moved accordingly.
+    fn sub_region(&self, offset: u64, length: u64) -> Region {
+        let delta = offset - self.offset;
+        Region {
+            offset,
+            length,
+            section: self.section.clone(),
+            virtual_address: self
+                .virtual_address
+                .map(|address| address.wrapping_add(delta)),
+            relative_virtual_address: self
+                .relative_virtual_address
+                .map(|address| address.wrapping_add(delta)),
+            resource: self.resource.clone(),
+            architecture: self.architecture.clone(),
+            cfstring_address: self.cfstring_address,
+            mapping: self.mapping.clone(),
+            parsed_string: None,
+            skip_if_zero: self.skip_if_zero,
+        }
+    }
+
+    /// Part of the region between `start_offset` and `end_offset`, if any. A parsed string is
+    /// kept only when it's entirely in the range.
+    pub(crate) fn clip(&self, start_offset: u64, end_offset: u64) -> Option<Region> {
+        let offset = self.offset.max(start_offset);
+        let end = self.offset.saturating_add(self.length).min(end_offset);
+        if offset >= end {
+            return None;
+        }
+        if self.parsed_string.is_some() {
+            let is_contained = offset == self.offset && end - offset == self.length;
+            return Some(self.clone()).filter(|_| is_contained);
+        }
+        Some(self.sub_region(offset, end - offset))
+    }
+
+    pub(crate) fn annotate(&self, extracted_string: &mut ExtractedString) {
+        let delta = extracted_string.offset - self.offset;
+        extracted_string.section = self.section.clone();
+        extracted_string.virtual_address = self
+            .virtual_address
+            .map(|address| address.wrapping_add(delta));
+        extracted_string.relative_virtual_address = self
+            .relative_virtual_address
+            .map(|address| address.wrapping_add(delta));
+        extracted_string.resource = self.resource.clone();
+        extracted_string.architecture = self.architecture.clone();
+        extracted_string.cfstring_address = self.cfstring_address;
+        extracted_string.mapping = self.mapping.clone();
+    }
+
+    /// The parsed string of the region, if it is at least `min_length` characters long.
+    pub(crate) fn get_parsed_string(
+        &self,
+        min_length: usize,
+        null_terminated_only: bool,
+    ) -> Option<ExtractedString> {
+        let parsed_string = self.parsed_string.as_ref()?;
+        if parsed_string.text.chars().count() < min_length.max(1) {
+            return None;
+        }
+        if null_terminated_only && parsed_string.null_terminated != Some(true) {
+            return None;
+        }
+        let mut extracted_string = ExtractedString {
+            text: parsed_string.text.clone(),
+            offset: self.offset,
+            end_offset: self.offset + self.length,
+            byte_length: self.length,
+            encoding: parsed_string.encoding,
+            null_terminated: parsed_string.null_terminated,
+            section: None,
+            virtual_address: None,
+            relative_virtual_address: None,
+            resource: None,
+            architecture: None,
+            cfstring_address: None,
+            mapping: None,
+            file_name: None,
+            member: None,
+            compression: None,
+        };
+        self.annotate(&mut extracted_string);
+        Some(extracted_string)
+    }
+}
+
+/// Regions of the input to scan, restricted to `sections` unless empty.
+pub(crate) fn find_regions<'data, R: ReadRef<'data>>(
+    binary_format: BinaryFormat,
+    data: R,
+    sections: &[String],
+) -> Result<Vec<Region>, Box<dyn Error>> {
+    let kind = FileKind::parse(data).ok();
+    let is_valid = match binary_format {
+        BinaryFormat::Raw => true,
+        BinaryFormat::Elf | BinaryFormat::Core => {
+            matches!(kind, Some(FileKind::Elf32 | FileKind::Elf64))
+        }
+        BinaryFormat::Pe => matches!(kind, Some(FileKind::Pe32 | FileKind::Pe64)),
+        BinaryFormat::MachO => matches!(
+            kind,
+            Some(
+                FileKind::MachO32 | FileKind::MachO64 | FileKind::MachOFat32 | FileKind::MachOFat64
+            )
+        ),
+    };
+    if !is_valid {
+        return Err(Box::new(InvalidBinaryFormatError::new(binary_format)));
+    }
+    let mut regions = match kind {
+        Some(FileKind::Elf32) if binary_format == BinaryFormat::Core => {
+            find_core_regions::<elf::FileHeader32<Endianness>, R>(data, sections)?
+        }
+        Some(FileKind::Elf64) if binary_format == BinaryFormat::Core => {
+            find_core_regions::<elf::FileHeader64<Endianness>, R>(data, sections)?
+        }
+        Some(FileKind::MachOFat32) => {
+            find_fat_regions(&MachOFatFile32::parse(data)?, data, sections)?
+        }
+        Some(FileKind::MachOFat64) => {
+            find_fat_regions(&MachOFatFile64::parse(data)?, data, sections)?
+        }
+        _ => find_file_regions(data, sections)?,
+    };
+    regions.sort_by_key(|region| region.offset);
+    Ok(regions)
+}
+
+/// Regions of a single executable.
+pub(crate) fn find_file_regions<'data, R: ReadRef<'data>>(
+    data: R,
+    sections: &[String],
+) -> Result<Vec<Region>, Box<dyn Error>> {
+    let file = object::File::parse(data)?;
+    let is_pe = matches!(file, object::File::Pe32(_) | object::File::Pe64(_));
+    let mut regions = vec![];
+    for section in file.sections() {
+        let name = match section.segment_name()? {
+            Some(segment_name) => format!("{},{}", segment_name, section.name()?),
+            None => section.name()?.to_owned(),
+        };
+        if !sections.is_empty() && !sections.contains(&name) {
+            continue;
+        }
+        // Sections without contents in the file, such as `.bss`, have no range
+        let (offset, length) = match section.file_range() {
+            Some((offset, length)) if length > 0 => (offset, length),
+            _ => continue,
+        };
+        let mut region = Region::new(offset, length);
+        region.section = Some(name);
+        // Sections that aren't loaded in memory, such as `.comment`, have no address
+        region.virtual_address = match section.flags() {
+            SectionFlags::Elf { sh_flags } if sh_flags & u64::from(elf::SHF_ALLOC) == 0 => None,
+            _ => Some(section.address()),
+        };
+        if is_pe {
+            region.relative_virtual_address =
+                Some(section.address().wrapping_sub(file.relative_address_base()));
+        }
+        regions.push(region);
+    }
+    let parsed_regions = match &file {
+        object::File::Pe32(pe_file) => find_resources(pe_file, data),
+        object::File::Pe64(pe_file) => find_resources(pe_file, data),
+        object::File::MachO32(_) | object::File::MachO64(_) => find_cfstrings(&file, data),
+        _ => ParsedRegions::default(),
+    };
+    let mut regions = insert_regions(regions, &parsed_regions.ranges, &parsed_regions.regions);
+    let architecture = find_architecture(&file);
+    for region in regions.iter_mut() {
+        region.architecture = architecture.clone();
+    }
+    Ok(regions)
+}
+
+/// Regions parsed from the structure of an executable, which replace the file `ranges` they were
+/// parsed from.
+#[derive(Default)]
+pub(crate) struct ParsedRegions {
+    pub(crate) ranges: Vec<(u64, u64)>,
+    pub(crate) regions: Vec<Region>,
+}
+
+/// Replaces the `ranges` of the regions with the `inserted` regions in them, which take the
+/// section and addresses of the region they are in.
+fn insert_regions(regions: Vec<Region>, ranges: &[(u64, u64)], inserted: &[Region]) -> Vec<Region> {
+    let mut ranges = ranges.to_vec();
+    ranges.sort();
+    let mut result = vec![];
+    for region in regions {
+        let region_end = region.offset + region.length;
+        let mut offset = region.offset;
+        for (range_offset, range_length) in &ranges {
+            let range_end = range_offset.saturating_add(*range_length).min(region_end);
+            let range_offset = (*range_offset).max(offset);
+            if range_offset >= range_end {
+                continue;
+            }
+            if offset < range_offset {
+                result.push(region.sub_region(offset, range_offset - offset));
+            }
+            offset = range_end;
+        }
+        if offset < region_end {
+            result.push(region.sub_region(offset, region_end - offset));
+        }
+        for inserted_region in inserted {
+            let inserted_end = inserted_region.offset + inserted_region.length;
+            if inserted_region.offset < region.offset || inserted_end > region_end {
+                continue;
+            }
+            let mut sub_region = region.sub_region(inserted_region.offset, inserted_region.length);
+            sub_region.resource = inserted_region.resource.clone();
+            sub_region.cfstring_address = inserted_region.cfstring_address;
+            sub_region.parsed_string = inserted_region.parsed_string.clone();
+            result.push(sub_region);
+        }
+    }
+    result
+}
@@ -0,0 +1,107 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Ascii characters that may be part of a string.
+///
+/// Characters outside of the ascii range are accepted according to the encoding (code page or
+/// unicode scripts), the character set only decides which of the first 128 code points count.
+///
+/// Examples:
+/// ```
+/// use rust_strings::CharacterSet;
+///
+/// // Terminal escape sequences
+/// let character_set = CharacterSet::gnu_default().with_char(0x1b);
+/// assert!(character_set.contains(0x1b));
+/// assert!(!character_set.contains(b'\n'));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CharacterSet {
+    characters: u128,
+}
+
+impl Default for CharacterSet {
+    fn default() -> Self {
+        Self::include_whitespace()
+    }
+}
+
+impl CharacterSet {
+    /// Printable ascii and tab, like GNU `strings` without `-w`.
+    pub fn gnu_default() -> Self {
+        Self::from_fn(|c| (32..=126).contains(&c) || c == b'\t')
+    }
+
+    /// Printable ascii, tab, LF and CR.
+    pub fn include_whitespace() -> Self {
+        Self::from_fn(|c| (32..=126).contains(&c) || (9..=10).contains(&c) || c == 13)
+    }
+
+    /// Ascii letters and digits only.
+    pub fn alphanumeric() -> Self {
+        Self::from_fn(|c| c.is_ascii_alphanumeric())
+    }
+
+    /// Character set of every ascii character matching `predicate`.
+    pub fn from_fn<F>(predicate: F) -> Self
+    where
+        F: Fn(u8) -> bool,
+    {
+        let characters = (0..128u8)
+            .filter(|c| predicate(*c))
+            .fold(0, |characters, c| characters | (1 << c));
+        CharacterSet { characters }
+    }
+
+    pub fn with_char(mut self, c: u8) -> Self {
+        if c.is_ascii() {
+            self.characters |= 1 << c;
+        }
+        self
+    }
+
+    pub fn without_char(mut self, c: u8) -> Self {
+        if c.is_ascii() {
+            self.characters &= !(1 << c);
+        }
+        self
+    }
+
+    pub fn contains(&self, c: u8) -> bool {
+        c.is_ascii() && self.characters & (1 << c) != 0
+    }
+}
+
+#[derive(Debug)]
+pub struct CharacterSetNotFoundError {
+    character_set: String,
+}
+
+impl fmt::Display for CharacterSetNotFoundError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Character set not found: {:?}", self.character_set)
+    }
+}
+
+impl CharacterSetNotFoundError {
+    fn new(character_set: String) -> Self {
+        CharacterSetNotFoundError { character_set }
+    }
+}
+
+impl Error for CharacterSetNotFoundError {}
+
+impl FromStr for CharacterSet {
+    type Err = CharacterSetNotFoundError;
+
+    fn from_str(character_set: &str) -> Result<Self, Self::Err> {
+        let character_set: &str = &character_set.to_lowercase();
+        match character_set {
+            "gnu" => Ok(CharacterSet::gnu_default()),
+            "whitespace" => Ok(CharacterSet::include_whitespace()),
+            "alphanumeric" => Ok(CharacterSet::alphanumeric()),
+            _ => Err(CharacterSetNotFoundError::new(character_set.to_owned())),
+        }
+    }
+}
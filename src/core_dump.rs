@@ -0,0 +1,110 @@
+use std::error::Error;
+
+use object::elf::{ET_CORE, NT_FILE, PT_LOAD, PT_NOTE};
+use object::read::elf::{FileHeader, ProgramHeader};
+use object::{Endian, ReadRef};
+
+use crate::binary_formats::{BinaryFormat, InvalidBinaryFormatError, Region};
+
+/// File mapped in the crashed process, read from the `NT_FILE` note.
+struct FileMapping {
+    start: u64,
+    end: u64,
+    path: String,
+}
+
+/// Regions of the `PT_LOAD` segments of a core dump, attributed to their virtual address and to
+/// the file mapped there. Segments that weren't dumped have no contents in the file and are
+/// skipped, the regions of dumped segments whose memory is zero-filled are skipped before being
+/// scanned. `mappings` restricts the segments to the files mapped at these paths unless empty.
+pub(crate) fn find_core_regions<'data, Elf: FileHeader, R: ReadRef<'data>>(
+    data: R,
+    mappings: &[String],
+) -> Result<Vec<Region>, Box<dyn Error>> {
+    let header = Elf::parse(data)?;
+    let endian = header.endian()?;
+    if header.e_type(endian) != ET_CORE {
+        return Err(Box::new(InvalidBinaryFormatError::new(BinaryFormat::Core)));
+    }
+    let segments = header.program_headers(endian, data)?;
+    let mut file_mappings = vec![];
+    for segment in segments {
+        if segment.p_type(endian) != PT_NOTE {
+            continue;
+        }
+        let mut notes = match segment.notes(endian, data)? {
+            Some(notes) => notes,
+            None => continue,
+        };
+        while let Some(note) = notes.next()? {
+            if note.name() == b"CORE" && note.n_type(endian) == NT_FILE {
+                file_mappings = parse_file_note(note.desc(), header.is_class_64(), endian);
+            }
+        }
+    }
+    let mut regions = vec![];
+    for segment in segments {
+        if segment.p_type(endian) != PT_LOAD {
+            continue;
+        }
+        let (offset, length) = segment.file_range(endian);
+        if length == 0 {
+            continue;
+        }
+        let address = segment.p_vaddr(endian).into();
+        let mapping = file_mappings
+            .iter()
+            .find(|file_mapping| file_mapping.start <= address && address < file_mapping.end)
+            .map(|file_mapping| file_mapping.path.clone());
+        if !mappings.is_empty() && !mapping.as_ref().is_some_and(|path| mappings.contains(path)) {
+            continue;
+        }
+        let mut region = Region::new(offset, length);
+        region.virtual_address = Some(address);
+        region.mapping = mapping;
+        region.skip_if_zero = true;
+        regions.push(region);
+    }
+    Ok(regions)
+}
+
+/// Files of an `NT_FILE` note: the number of files and the page size, then the start, end and
+/// file offset of every file, then their null-terminated paths. A truncated note yields the files
+/// read so far.
+fn parse_file_note(desc: &[u8], is_class_64: bool, endian: impl Endian) -> Vec<FileMapping> {
+    let word_size = if is_class_64 { 8 } else { 4 };
+    let read_word = |index: usize| -> Option<u64> {
+        let bytes = desc.get(index * word_size..(index + 1) * word_size)?;
+        Some(match is_class_64 {
+            true => endian.read_u64_bytes(bytes.try_into().ok()?),
+            false => endian.read_u32_bytes(bytes.try_into().ok()?).into(),
+        })
+    };
+    let count = read_word(0).unwrap_or(0) as usize;
+    let paths_start = count
+        .checked_mul(3)
+        .and_then(|words| words.checked_add(2))
+        .and_then(|words| words.checked_mul(word_size))
+        .unwrap_or(usize::MAX);
+    let mut paths = desc
+        .get(paths_start..)
+        .unwrap_or_default()
+        .split(|byte| *byte == 0);
+    let mut file_mappings = vec![];
+    for index in 0..count {
+        let (start, end, path) = match (
+            read_word(2 + index * 3),
+            read_word(3 + index * 3),
+            paths.next(),
+        ) {
+            (Some(start), Some(end), Some(path)) => (start, end, path),
+            _ => break,
+        };
+        file_mappings.push(FileMapping {
+            start,
+            end,
+            path: String::from_utf8_lossy(path).into_owned(),
+        });
+    }
+    file_mappings
+}
@@ -0,0 +1,132 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read};
+#[cfg(any(feature = "gzip", feature = "xz", feature = "zstd", feature = "bzip2"))]
+use std::io::{BufWriter, Write};
+#[cfg(not(any(feature = "gzip", feature = "xz", feature = "zstd", feature = "bzip2")))]
+use std::path::Path;
+
+#[cfg(any(feature = "gzip", feature = "xz", feature = "zstd", feature = "bzip2"))]
+pub(crate) use tempfile::NamedTempFile;
+
+/// Number of bytes read to detect the compression of an input.
+const MAGIC_LENGTH: u64 = 6;
+
+/// Whether a compressed input is decompressed before extracting strings.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Decompression {
+    /// The input is scanned as is
+    #[default]
+    None,
+    /// The compression is detected from the first bytes of the input, a compressed input is
+    /// scanned as its decompressed stream and others as is. Only the formats whose cargo feature
+    /// is enabled are detected: `gzip` (enabled by default), `xz`, `zstd` and `bzip2`
+    Auto,
+}
+
+/// Compression format of an input scanned with [`Decompression::Auto`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Xz,
+    Zstd,
+    Bzip2,
+}
+
+impl fmt::Display for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Compression {
+    /// Whether the cargo feature decompressing the format is enabled.
+    fn is_enabled(self) -> bool {
+        match self {
+            Compression::Gzip => cfg!(feature = "gzip"),
+            Compression::Xz => cfg!(feature = "xz"),
+            Compression::Zstd => cfg!(feature = "zstd"),
+            Compression::Bzip2 => cfg!(feature = "bzip2"),
+        }
+    }
+}
+
+/// Compression of the input whose first bytes are `header`, `None` if it isn't compressed or its
+/// format isn't enabled.
+pub(crate) fn detect_compression(header: &[u8]) -> Option<Compression> {
+    let compression = match header {
+        [0x1f, 0x8b, ..] => Compression::Gzip,
+        [0xfd, b'7', b'z', b'X', b'Z', 0x00, ..] => Compression::Xz,
+        [0x28, 0xb5, 0x2f, 0xfd, ..] => Compression::Zstd,
+        [b'B', b'Z', b'h', b'1'..=b'9', ..] => Compression::Bzip2,
+        _ => return None,
+    };
+    compression.is_enabled().then_some(compression)
+}
+
+/// Reads the first bytes of an input and detects its compression.
+pub(crate) fn read_compression(reader: impl Read) -> Result<Option<Compression>, Box<dyn Error>> {
+    let mut header = vec![];
+    reader.take(MAGIC_LENGTH).read_to_end(&mut header)?;
+    Ok(detect_compression(&header))
+}
+
+/// Reader over the decompressed stream of a compressed input, concatenated streams are read one
+/// after the other.
+#[cfg_attr(
+    not(any(feature = "gzip", feature = "xz", feature = "zstd", feature = "bzip2")),
+    allow(unused_variables)
+)]
+pub(crate) fn decompress<'a>(
+    compression: Compression,
+    reader: Box<dyn BufRead + 'a>,
+    buffer_size: usize,
+) -> Result<Box<dyn BufRead + 'a>, Box<dyn Error>> {
+    let decoder: Result<Box<dyn Read + 'a>, Box<dyn Error>> = match compression {
+        #[cfg(feature = "gzip")]
+        Compression::Gzip => Ok(Box::new(flate2::bufread::MultiGzDecoder::new(reader))),
+        #[cfg(feature = "xz")]
+        Compression::Xz => Ok(Box::new(xz2::bufread::XzDecoder::new_multi_decoder(reader))),
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => Ok(Box::new(zstd::stream::read::Decoder::with_buffer(reader)?)),
+        #[cfg(feature = "bzip2")]
+        Compression::Bzip2 => Ok(Box::new(bzip2::bufread::MultiBzDecoder::new(reader))),
+        #[allow(unreachable_patterns)]
+        compression => Err(format!("{} decompression isn't enabled", compression).into()),
+    };
+    Ok(Box::new(BufReader::with_capacity(buffer_size, decoder?)))
+}
+
+/// Writes a decompressed stream to a temporary file, which can then be read from any offset.
+#[cfg(any(feature = "gzip", feature = "xz", feature = "zstd", feature = "bzip2"))]
+pub(crate) fn write_temp_file(
+    mut reader: impl Read,
+    buffer_size: usize,
+) -> Result<NamedTempFile, Box<dyn Error>> {
+    let mut file = NamedTempFile::new()?;
+    let mut writer = BufWriter::with_capacity(buffer_size, file.as_file_mut());
+    io::copy(&mut reader, &mut writer)?;
+    writer.flush()?;
+    drop(writer);
+    Ok(file)
+}
+
+/// Temporary file of a decompressed stream, which never exists without a compression format.
+#[cfg(not(any(feature = "gzip", feature = "xz", feature = "zstd", feature = "bzip2")))]
+#[doc(hidden)]
+pub enum NamedTempFile {}
+
+#[cfg(not(any(feature = "gzip", feature = "xz", feature = "zstd", feature = "bzip2")))]
+impl NamedTempFile {
+    pub(crate) fn path(&self) -> &Path {
+        match *self {}
+    }
+}
+
+#[cfg(not(any(feature = "gzip", feature = "xz", feature = "zstd", feature = "bzip2")))]
+pub(crate) fn write_temp_file(
+    _reader: impl Read,
+    _buffer_size: usize,
+) -> Result<NamedTempFile, Box<dyn Error>> {
+    Err(io::Error::other("No compression format is enabled").into())
+}
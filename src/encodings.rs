@@ -2,11 +2,25 @@ use std::error::Error;
 use std::fmt;
 use std::str::FromStr;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Encoding {
     ASCII,
+    UTF8,
     UTF16LE,
     UTF16BE,
+    UTF32LE,
+    UTF32BE,
+    LATIN1,
+    CP1252,
+    CP437,
+    KOI8R,
+    CP037,
+    CP500,
+    CP1047,
+    SHIFTJIS,
+    GB18030,
+    BIG5,
+    EUCKR,
 }
 
 impl fmt::Display for Encoding {
@@ -42,9 +56,22 @@ impl FromStr for Encoding {
         match encoding {
             "utf-16le" => Ok(Encoding::UTF16LE),
             "utf-16be" => Ok(Encoding::UTF16BE),
+            "utf-32le" => Ok(Encoding::UTF32LE),
+            "utf-32be" => Ok(Encoding::UTF32BE),
             "ascii" => Ok(Encoding::ASCII),
-            "utf8" => Ok(Encoding::ASCII),
-            "utf-8" => Ok(Encoding::ASCII),
+            "latin-1" | "latin1" | "iso-8859-1" => Ok(Encoding::LATIN1),
+            "cp1252" | "windows-1252" => Ok(Encoding::CP1252),
+            "cp437" => Ok(Encoding::CP437),
+            "koi8-r" | "koi8r" => Ok(Encoding::KOI8R),
+            "cp037" | "ebcdic" => Ok(Encoding::CP037),
+            "cp500" => Ok(Encoding::CP500),
+            "cp1047" => Ok(Encoding::CP1047),
+            "shift-jis" | "shift_jis" | "sjis" => Ok(Encoding::SHIFTJIS),
+            "gbk" | "gb18030" => Ok(Encoding::GB18030),
+            "big5" => Ok(Encoding::BIG5),
+            "euc-kr" => Ok(Encoding::EUCKR),
+            "utf8" => Ok(Encoding::UTF8),
+            "utf-8" => Ok(Encoding::UTF8),
             _ => Err(EncodingNotFoundError::new(encoding.to_owned())),
         }
     }
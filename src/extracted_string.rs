@@ -0,0 +1,50 @@
+use crate::decompression::Compression;
+use crate::encodings::Encoding;
+
+/// A string found in the input.
+///
+/// New fields are added as the crate learns to locate strings in more kinds of inputs, so the
+/// struct can't be built outside of the crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ExtractedString {
+    pub text: String,
+    /// Offset of the first byte of the string
+    pub offset: u64,
+    /// Offset right after the last byte of the string
+    pub end_offset: u64,
+    /// Length of the string in bytes, as opposed to the length of `text` in characters
+    pub byte_length: u64,
+    pub encoding: Encoding,
+    /// Whether the string is followed by a null terminator, `None` if it ends with the input
+    pub null_terminated: Option<bool>,
+    /// Section of the executable the string is in, when parsed with a
+    /// [`BinaryFormat`](crate::BinaryFormat)
+    pub section: Option<String>,
+    /// Address of the string once the executable is loaded
+    pub virtual_address: Option<u64>,
+    /// Address of the string relative to the image base of a PE file
+    pub relative_virtual_address: Option<u64>,
+    /// Path of the PE resource the string is in, `type/name/language` followed by the string id
+    /// for string tables or the string table and key for version info, e.g.
+    /// `RT_VERSION/1/1033/040904B0/CompanyName`
+    pub resource: Option<String>,
+    /// Architecture of the Mach-O file the string is in, e.g. `arm64` in a fat binary
+    pub architecture: Option<String>,
+    /// Address of the CFString object whose text is the string
+    pub cfstring_address: Option<u64>,
+    /// Mapping of the process the string is in, the path of the mapped file or a name such as
+    /// `[heap]`, with a [`ProcessConfig`](crate::ProcessConfig). `None` for anonymous mappings
+    pub mapping: Option<String>,
+    /// File the string was found in, set for inputs of several files such as a
+    /// [`DirectoryConfig`](crate::DirectoryConfig)
+    pub file_name: Option<String>,
+    /// Path of the archive member the string was found in, members of nested archives are
+    /// joined with `/`, e.g. `firmware.tar/rootfs/bin/busybox`
+    pub member: Option<String>,
+    /// Compression of the input the string was decompressed from, with
+    /// [`Decompression::Auto`](crate::Decompression::Auto). Offsets are then in the decompressed
+    /// stream, and the members of a compressed archive such as a `.tar.gz` carry its compression
+    /// unless they are compressed themselves
+    pub compression: Option<Compression>,
+}
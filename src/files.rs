@@ -0,0 +1,107 @@
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use walkdir::WalkDir;
+
+/// A file of a multi-file input couldn't be scanned, the other files are scanned anyway.
+#[derive(Debug)]
+pub struct FileError {
+    file_name: String,
+    error: Box<dyn Error>,
+}
+
+impl fmt::Display for FileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.file_name, self.error)
+    }
+}
+
+impl FileError {
+    pub(crate) fn new(file_name: String, error: Box<dyn Error>) -> Self {
+        FileError { file_name, error }
+    }
+
+    /// The error of a member, prefixed by the path of the file or archive member holding it.
+    pub(crate) fn in_parent(mut self, parent_name: &str) -> Self {
+        self.file_name = format!("{}/{}", parent_name, self.file_name);
+        self
+    }
+
+    /// Path of the file as it was found when walking the input, followed by the path of the
+    /// member for a file inside an archive.
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+}
+
+impl Error for FileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.error.as_ref())
+    }
+}
+
+/// Files of a multi-file input, or the entries that couldn't be read in place of their files.
+pub(crate) type Files = Vec<Result<PathBuf, FileError>>;
+
+/// How the files of a multi-file input are found.
+pub(crate) struct FileFilter<'a> {
+    pub(crate) recursive: bool,
+    pub(crate) follow_symlinks: bool,
+    pub(crate) include: &'a [String],
+    pub(crate) exclude: &'a [String],
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet, Box<dyn Error>> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern)?);
+    }
+    Ok(builder.build()?)
+}
+
+/// Files to scan under `paths`, sorted by name within every directory. Paths given explicitly are
+/// scanned even when they don't match the filter, an entry that can't be read is returned as
+/// an error in place of its files.
+pub(crate) fn find_files(paths: &[PathBuf], filter: &FileFilter) -> Result<Files, Box<dyn Error>> {
+    let include = build_glob_set(filter.include)?;
+    let exclude = build_glob_set(filter.exclude)?;
+    let mut files = vec![];
+    for path in paths {
+        let mut walk_dir = WalkDir::new(path)
+            .follow_links(filter.follow_symlinks)
+            .sort_by_file_name();
+        if !filter.recursive {
+            walk_dir = walk_dir.max_depth(1);
+        }
+        let entries = walk_dir
+            .into_iter()
+            .filter_entry(|entry| entry.depth() == 0 || !exclude.is_match(entry.path()));
+        for entry in entries {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    let file_name = err.path().unwrap_or(path).to_string_lossy().into_owned();
+                    files.push(Err(FileError::new(file_name, Box::new(err))));
+                    continue;
+                }
+            };
+            if entry.file_type().is_dir() {
+                continue;
+            }
+            if entry.depth() > 0 {
+                // Symlinks that aren't followed, pipes and devices are scanned only when given
+                // explicitly
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                if !include.is_empty() && !include.is_match(entry.path()) {
+                    continue;
+                }
+            }
+            files.push(Ok(entry.into_path()));
+        }
+    }
+    Ok(files)
+}
@@ -35,15 +35,44 @@
 
 use std::error::Error;
 
+mod archives;
+mod binary_formats;
+mod character_set;
+mod code_pages;
+mod core_dump;
+mod decompression;
 mod encodings;
+mod extracted_string;
+mod files;
+mod macho;
+mod max_length;
+mod parallel;
+mod pe_resources;
+#[cfg(target_os = "linux")]
+mod process;
 mod strings;
 mod strings_extractor;
 mod strings_writer;
+mod unicode_scripts;
 
 type ErrorResult = Result<(), Box<dyn Error>>;
 
+pub use archives::MemberTooLargeError;
+pub use binary_formats::{BinaryFormat, BinaryFormatNotFoundError, InvalidBinaryFormatError};
+pub use character_set::{CharacterSet, CharacterSetNotFoundError};
+pub use decompression::{Compression, Decompression};
 pub use encodings::{Encoding, EncodingNotFoundError};
-pub use strings::{dump_strings, strings, BytesConfig, Config, FileConfig, StdinConfig};
+pub use extracted_string::ExtractedString;
+pub use files::FileError;
+pub use max_length::{MaxLengthPolicy, MaxLengthPolicyNotFoundError};
+#[cfg(target_os = "linux")]
+pub use strings::ProcessConfig;
+pub use strings::{
+    dump_extracted_strings, dump_strings, extract_into, extract_strings, strings, strings_iter,
+    BytesConfig, Config, DirectoryConfig, FileConfig, ReaderConfig, StdinConfig, StringsIter,
+};
+pub use strings_writer::{JsonWriter, StringWriter, VectorWriter};
+pub use unicode_scripts::{UnicodeScript, UnicodeScriptNotFoundError};
 
 #[cfg(feature = "python_bindings")]
 mod python_bindings;
@@ -0,0 +1,179 @@
+use std::error::Error;
+
+use object::macho;
+use object::read::macho::{FatArch, MachHeader, MachOFatFile};
+use object::{Object, ObjectSection, ObjectSegment, ReadRef};
+
+use crate::binary_formats::{find_file_regions, ParsedRegions, ParsedString, Region};
+use crate::encodings::Encoding;
+
+/// Regions of every architecture of a fat binary, with offsets relative to the fat binary.
+pub(crate) fn find_fat_regions<'data, Fat: FatArch, R: ReadRef<'data>>(
+    fat_file: &MachOFatFile<'data, Fat>,
+    data: R,
+    sections: &[String],
+) -> Result<Vec<Region>, Box<dyn Error>> {
+    let mut regions = vec![];
+    for arch in fat_file.arches() {
+        let (arch_offset, _) = arch.file_range();
+        let mut arch_regions = find_file_regions(arch.data(data)?, sections)?;
+        for region in arch_regions.iter_mut() {
+            region.offset += arch_offset;
+        }
+        regions.extend(arch_regions);
+    }
+    Ok(regions)
+}
+
+/// Name of the architecture of a Mach-O file, like `lipo` names it.
+pub(crate) fn find_architecture<'data, R: ReadRef<'data>>(
+    file: &object::File<'data, R>,
+) -> Option<String> {
+    let (cpu_type, cpu_subtype) = match file {
+        object::File::MachO32(file) => {
+            let header = file.macho_header();
+            (
+                header.cputype(file.endian()),
+                header.cpusubtype(file.endian()),
+            )
+        }
+        object::File::MachO64(file) => {
+            let header = file.macho_header();
+            (
+                header.cputype(file.endian()),
+                header.cpusubtype(file.endian()),
+            )
+        }
+        _ => return None,
+    };
+    let architecture = match (cpu_type, cpu_subtype & !macho::CPU_SUBTYPE_MASK) {
+        (macho::CPU_TYPE_X86, _) => "i386",
+        (macho::CPU_TYPE_X86_64, macho::CPU_SUBTYPE_X86_64_H) => "x86_64h",
+        (macho::CPU_TYPE_X86_64, _) => "x86_64",
+        (macho::CPU_TYPE_ARM, _) => "arm",
+        (macho::CPU_TYPE_ARM64, macho::CPU_SUBTYPE_ARM64E) => "arm64e",
+        (macho::CPU_TYPE_ARM64, _) => "arm64",
+        (macho::CPU_TYPE_ARM64_32, _) => "arm64_32",
+        (macho::CPU_TYPE_POWERPC, _) => "ppc",
+        (macho::CPU_TYPE_POWERPC64, _) => "ppc64",
+        _ => return Some(format!("{:#x}", cpu_type)),
+    };
+    Some(String::from(architecture))
+}
+
+/// CFString objects of the `__cfstring` sections, resolved to the text they point at.
+pub(crate) fn find_cfstrings<'data, R: ReadRef<'data>>(
+    file: &object::File<'data, R>,
+    data: R,
+) -> ParsedRegions {
+    let mut parsed_regions = ParsedRegions::default();
+    let word_size = if file.is_64() { 8 } else { 4 };
+    let little_endian = file.is_little_endian();
+    let read_word = |bytes: &[u8]| {
+        let mut word = [0; 8];
+        match little_endian {
+            true => {
+                word[..bytes.len()].copy_from_slice(bytes);
+                u64::from_le_bytes(word)
+            }
+            false => {
+                word[8 - bytes.len()..].copy_from_slice(bytes);
+                u64::from_be_bytes(word)
+            }
+        }
+    };
+    let image_base = file
+        .segments()
+        .find(|segment| segment.name() == Ok(Some("__TEXT")))
+        .map_or(0, |segment| segment.address());
+    let mapped_sections: Vec<(u64, u64, u64)> = file
+        .sections()
+        .filter_map(|section| {
+            let (offset, length) = section.file_range()?;
+            Some((section.address(), offset, length))
+        })
+        .collect();
+    // Offset in the file of `length` bytes at `address`
+    let to_offset = |address: u64, length: u64| {
+        mapped_sections
+            .iter()
+            .find_map(|(section_address, offset, section_length)| {
+                let delta = address.checked_sub(*section_address)?;
+                offset
+                    .checked_add(delta)
+                    .filter(|_| delta.saturating_add(length) <= *section_length)
+            })
+    };
+    for section in file.sections() {
+        if section.name() != Ok("__cfstring") {
+            continue;
+        }
+        let contents = match section.data() {
+            Ok(contents) => contents,
+            Err(_) => continue,
+        };
+        // isa, flags, pointer to the text and its length in characters
+        for (i, cfstring) in contents.chunks_exact(word_size * 4).enumerate() {
+            let flags = read_word(&cfstring[word_size..word_size + 4]);
+            let pointer = read_word(&cfstring[word_size * 2..word_size * 3]);
+            let length = read_word(&cfstring[word_size * 3..word_size * 4]);
+            let is_utf16 = flags & 0x10 != 0;
+            // Lengths and pointers are read from the file, a CFString overflowing them is skipped
+            let byte_length = match is_utf16 {
+                true => length.checked_mul(2),
+                false => Some(length),
+            };
+            let byte_length = match byte_length {
+                Some(byte_length) if length != 0 => byte_length,
+                _ => continue,
+            };
+            // Pointers rebased with chained fixups keep their target in the low 36 bits
+            let target = pointer & 0xf_ffff_ffff;
+            let offset = match [Some(pointer), Some(target), image_base.checked_add(target)]
+                .into_iter()
+                .flatten()
+                .find_map(|address| to_offset(address, byte_length))
+            {
+                Some(offset) => offset,
+                None => continue,
+            };
+            let (bytes, terminator) = match (
+                data.read_bytes_at(offset, byte_length),
+                data.read_bytes_at(
+                    offset.saturating_add(byte_length),
+                    if is_utf16 { 2 } else { 1 },
+                ),
+            ) {
+                (Ok(bytes), terminator) => (bytes, terminator.ok()),
+                (Err(_), _) => continue,
+            };
+            let (text, encoding) = match (is_utf16, little_endian) {
+                (true, true) => (decode_utf16(bytes, u16::from_le_bytes), Encoding::UTF16LE),
+                (true, false) => (decode_utf16(bytes, u16::from_be_bytes), Encoding::UTF16BE),
+                (false, _) if bytes.is_ascii() => {
+                    (String::from_utf8_lossy(bytes).into_owned(), Encoding::ASCII)
+                }
+                (false, _) => (String::from_utf8_lossy(bytes).into_owned(), Encoding::UTF8),
+            };
+            let mut region = Region::new(offset, byte_length);
+            region.cfstring_address = section.address().checked_add((i * word_size * 4) as u64);
+            region.parsed_string = Some(ParsedString {
+                text,
+                encoding,
+                null_terminated: terminator.map(|terminator| terminator.iter().all(|c| *c == 0)),
+            });
+            parsed_regions.ranges.push((offset, byte_length));
+            parsed_regions.regions.push(region);
+        }
+    }
+    parsed_regions
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> String {
+    let units = bytes
+        .chunks_exact(2)
+        .map(|unit| from_bytes([unit[0], unit[1]]));
+    char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+}
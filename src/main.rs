@@ -1,78 +1,358 @@
 use clap::Parser;
-use rust_strings::{strings, Encoding, FileConfig, StdinConfig};
-use std::path::Path;
+#[cfg(target_os = "linux")]
+use rust_strings::ProcessConfig;
+use rust_strings::{
+    extract_into, BinaryFormat, CharacterSet, Decompression, DirectoryConfig, Encoding,
+    ExtractedString, FileConfig, FileError, MaxLengthPolicy, StdinConfig, StringWriter,
+    UnicodeScript,
+};
+use std::error::Error;
+use std::io::{BufWriter, StdoutLock, Write};
+use std::path::{Path, PathBuf};
 use std::process::exit;
 use std::str::FromStr;
 
 #[derive(Parser, Debug)]
 #[clap(version = "1.0", author = "Iddo Hauschner", name = "rust-strings")]
 struct Opts {
-    /// file path to run strings on, use "-" for stdin
+    /// file paths or directories to run strings on, use "-" for stdin
     #[clap(name = "FILE_PATH_ARG")]
-    file_path_arg: Option<String>,
+    file_path_arg: Vec<String>,
     /// file path to run strings on, use "-" for stdin
     #[clap(short, long, name = "FILE_PATH")]
     file_path_flag: Option<String>,
     /// min length of string
     #[clap(short, long, default_value = "3")]
     min_length: usize,
-    /// encoding of string
-    #[clap(short, long, default_value = "ascii")]
-    encoding: String,
+    /// max length of string, longer strings are handled according to --max-length-policy
+    #[clap(long)]
+    max_length: Option<usize>,
+    /// what happens to strings longer than --max-length: "drop", "truncate" (cut and ended with
+    /// "...") or "split" (consecutive chunks)
+    #[clap(long, default_value = "truncate")]
+    max_length_policy: String,
+    /// print only the strings ended by a null character of their encoding
+    #[clap(long)]
+    null_terminated_only: bool,
+    /// encodings of strings, e.g. "ascii,utf-16le"
+    #[clap(short, long, default_value = "ascii", value_delimiter = ',')]
+    encoding: Vec<String>,
+    /// ascii characters allowed in strings: "gnu" (printable and tab), "whitespace" (printable,
+    /// tab, LF and CR) or "alphanumeric"
+    #[clap(short, long, default_value = "whitespace")]
+    character_set: String,
+    /// unicode scripts allowed in utf-16 strings besides ascii, e.g. "cyrillic,han"
+    #[clap(short, long, value_delimiter = ',')]
+    unicode_scripts: Vec<String>,
+    #[clap(short, long)]
+    offset: bool,
+    /// print the encoding of every string
+    #[clap(long)]
+    show_encoding: bool,
+    /// map the file into memory instead of reading it with a buffer, only faster with several threads
+    #[clap(long)]
+    mmap: bool,
+    /// number of threads scanning the input, 0 uses every core
+    #[clap(short, long, default_value = "1")]
+    threads: usize,
+    /// offset to start scanning from
+    #[clap(long, default_value = "0")]
+    start_offset: u64,
+    /// offset to stop scanning at
+    #[clap(long)]
+    end_offset: Option<u64>,
+    /// parse the file as an executable and scan its sections only: "raw", "elf", "pe", "macho" or
+    /// "core" for ELF core dumps, whose sections are the paths of mapped files
+    #[clap(long, default_value = "raw")]
+    format: String,
+    /// sections to scan when parsing an executable, Mach-O sections are named with their segment,
+    /// e.g. ".rodata,.data" or "__TEXT,__cstring"
+    #[clap(long, value_delimiter = ',')]
+    sections: Vec<String>,
+    /// print the section, virtual address, pe resource, mach-o architecture and process mapping of
+    /// every string
+    #[clap(long)]
+    show_section: bool,
+    /// scan the files of subdirectories too
     #[clap(short, long)]
+    recursive: bool,
+    /// glob of the files to scan in directories, e.g. "*.dll", can be repeated
+    #[clap(long)]
+    include: Vec<String>,
+    /// glob of the files and directories to skip, e.g. "**/.git", can be repeated
+    #[clap(long)]
+    exclude: Vec<String>,
+    /// follow symlinks found in directories
+    #[clap(long)]
+    follow_symlinks: bool,
+    /// print the name of the file before every string, and the path of the archive member
+    #[clap(long)]
+    print_file_name: bool,
+    /// scan the members of zip and tar archives instead of the archives
+    #[clap(long)]
+    archives: bool,
+    /// levels of nested archives to descend into
+    #[clap(long, default_value = "4")]
+    max_archive_depth: usize,
+    /// decompressed size in bytes above which an archive member isn't scanned
+    #[clap(long, default_value = "268435456")]
+    max_member_size: u64,
+    /// scan the decompressed stream of gzip, xz, zstd and bzip2 files, offsets are in the
+    /// decompressed stream
+    #[clap(long)]
+    decompress: bool,
+    /// scan the memory of a running process instead of files, offsets are virtual addresses
+    /// (linux only)
+    #[clap(long)]
+    pid: Option<u32>,
+}
+
+/// Prints strings as soon as they are extracted.
+struct PrintWriter<'a> {
+    output: BufWriter<StdoutLock<'a>>,
     offset: bool,
+    show_encoding: bool,
+    show_section: bool,
+    print_file_name: bool,
+    has_file_errors: bool,
 }
 
-fn get_file_path(options: &Opts) -> String {
-    if matches!(options.file_path_arg, Some(_)) && matches!(options.file_path_flag, Some(_)) {
+impl StringWriter for PrintWriter<'_> {
+    fn write_string(&mut self, extracted_string: ExtractedString) -> Result<(), Box<dyn Error>> {
+        if self.print_file_name {
+            let names: Vec<&str> = [&extracted_string.file_name, &extracted_string.member]
+                .into_iter()
+                .filter_map(|name| name.as_deref())
+                .collect();
+            if !names.is_empty() {
+                write!(self.output, "{}: ", names.join("/"))?;
+            }
+        }
+        if self.offset {
+            write!(self.output, "{:10}: ", extracted_string.offset)?;
+        }
+        if self.show_encoding {
+            write!(self.output, "{:8} ", extracted_string.encoding.to_string())?;
+        }
+        if self.show_section {
+            let section = extracted_string.section.unwrap_or_default();
+            let virtual_address = extracted_string
+                .virtual_address
+                .map(|virtual_address| format!("{:#x}", virtual_address))
+                .unwrap_or_default();
+            if let Some(architecture) = extracted_string.architecture {
+                write!(self.output, "{:8} ", architecture)?;
+            }
+            write!(self.output, "{:16} {:>18} ", section, virtual_address)?;
+            if let Some(resource) = extracted_string.resource {
+                write!(self.output, "{} ", resource)?;
+            }
+            if let Some(mapping) = extracted_string.mapping {
+                write!(self.output, "{} ", mapping)?;
+            }
+        }
+        writeln!(self.output, "{}", extracted_string.text)?;
+        Ok(())
+    }
+
+    fn write_error(&mut self, file_error: FileError) -> Result<(), Box<dyn Error>> {
+        self.output.flush()?;
+        eprintln!("{}", file_error);
+        self.has_file_errors = true;
+        Ok(())
+    }
+}
+
+fn get_file_paths(options: &Opts) -> Vec<String> {
+    if !options.file_path_arg.is_empty() && options.file_path_flag.is_some() {
         eprintln!("You can't specify file path as argument and as flag together");
         exit(1);
     }
-    let mut file_path = String::new();
-    if let Some(file_path_arg) = &options.file_path_arg {
-        file_path = file_path_arg.clone()
-    }
-    if let Some(file_path_flag) = &options.file_path_flag {
-        file_path = file_path_flag.clone()
+    match &options.file_path_flag {
+        Some(file_path_flag) => vec![file_path_flag.clone()],
+        None => options.file_path_arg.clone(),
     }
-    file_path
 }
 
 fn main() {
     let options = Opts::parse();
-    let encoding = match Encoding::from_str(&options.encoding) {
-        Ok(encoding) => encoding,
+    let encodings = match options
+        .encoding
+        .iter()
+        .map(|e| Encoding::from_str(e))
+        .collect::<Result<Vec<Encoding>, _>>()
+    {
+        Ok(encodings) => encodings,
+        Err(err) => {
+            eprintln!("{}", err);
+            exit(1);
+        }
+    };
+    let character_set = match CharacterSet::from_str(&options.character_set) {
+        Ok(character_set) => character_set,
+        Err(err) => {
+            eprintln!("{}", err);
+            exit(1);
+        }
+    };
+    let unicode_scripts = match options
+        .unicode_scripts
+        .iter()
+        .map(|s| UnicodeScript::from_str(s))
+        .collect::<Result<Vec<UnicodeScript>, _>>()
+    {
+        Ok(unicode_scripts) => unicode_scripts,
+        Err(err) => {
+            eprintln!("{}", err);
+            exit(1);
+        }
+    };
+    let binary_format = match BinaryFormat::from_str(&options.format) {
+        Ok(binary_format) => binary_format,
         Err(err) => {
             eprintln!("{}", err);
             exit(1);
         }
     };
-    let file_path = get_file_path(&options);
-    let extracted_strings = match file_path == "-" {
-        true => strings(
-            &StdinConfig::new()
+    let max_length_policy = match MaxLengthPolicy::from_str(&options.max_length_policy) {
+        Ok(max_length_policy) => max_length_policy,
+        Err(err) => {
+            eprintln!("{}", err);
+            exit(1);
+        }
+    };
+    let decompression = match options.decompress {
+        true => Decompression::Auto,
+        false => Decompression::None,
+    };
+    let file_paths = get_file_paths(&options);
+    let mut print_writer = PrintWriter {
+        output: BufWriter::new(std::io::stdout().lock()),
+        offset: options.offset,
+        show_encoding: options.show_encoding,
+        show_section: options.show_section,
+        print_file_name: options.print_file_name,
+        has_file_errors: false,
+    };
+    if options.pid.is_some() && !file_paths.is_empty() {
+        eprintln!("A process can't be scanned along with files");
+        exit(1);
+    }
+    if options.pid.is_none() && file_paths.is_empty() {
+        eprintln!("You must specify a file path, \"-\" for stdin, or --pid");
+        exit(1);
+    }
+    if options.pid.is_some() && binary_format != BinaryFormat::Raw {
+        eprintln!("Binary formats can't be parsed from a process");
+        exit(1);
+    }
+    let is_stdin = file_paths.iter().any(|file_path| file_path == "-");
+    if is_stdin && file_paths.len() > 1 {
+        eprintln!("Stdin can't be scanned along with other files");
+        exit(1);
+    }
+    if is_stdin && binary_format != BinaryFormat::Raw {
+        eprintln!("Binary formats can't be parsed from stdin");
+        exit(1);
+    }
+    // The strings of a single file carry no file name, scan it like several files to print it
+    let is_single_file = match file_paths.as_slice() {
+        [file_path] => !Path::new(file_path).is_dir() && !options.print_file_name,
+        _ => false,
+    };
+    let result = match options.pid {
+        #[cfg(target_os = "linux")]
+        Some(pid) => {
+            let mut config = ProcessConfig::new(pid)
+                .with_min_length(options.min_length)
+                .with_null_terminated_only(options.null_terminated_only)
+                .with_encodings(encodings)
+                .with_character_set(character_set)
+                .with_unicode_scripts(unicode_scripts)
+                .with_threads(options.threads)
+                .with_start_offset(options.start_offset)
+                .with_end_offset(options.end_offset.unwrap_or(u64::MAX));
+            if let Some(max_length) = options.max_length {
+                config = config.with_max_length(max_length, max_length_policy);
+            }
+            extract_into(&config, &mut print_writer)
+        }
+        #[cfg(not(target_os = "linux"))]
+        Some(_) => Err("Process memory can only be scanned on Linux".into()),
+        None if is_stdin => {
+            let mut config = StdinConfig::new()
+                .with_min_length(options.min_length)
+                .with_null_terminated_only(options.null_terminated_only)
+                .with_encodings(encodings)
+                .with_character_set(character_set)
+                .with_unicode_scripts(unicode_scripts)
+                .with_threads(options.threads)
+                .with_start_offset(options.start_offset)
+                .with_end_offset(options.end_offset.unwrap_or(u64::MAX));
+            if let Some(max_length) = options.max_length {
+                config = config.with_max_length(max_length, max_length_policy);
+            }
+            extract_into(&config, &mut print_writer)
+        }
+        None if !is_single_file => {
+            let mut config = DirectoryConfig::new(file_paths.iter().map(PathBuf::from).collect())
+                .with_recursive(options.recursive)
+                .with_include(options.include)
+                .with_exclude(options.exclude)
+                .with_follow_symlinks(options.follow_symlinks)
                 .with_min_length(options.min_length)
-                .with_encoding(encoding),
-        ),
-        false => {
-            let path: &Path = Path::new(&file_path);
+                .with_null_terminated_only(options.null_terminated_only)
+                .with_encodings(encodings)
+                .with_character_set(character_set)
+                .with_unicode_scripts(unicode_scripts)
+                .with_mmap(options.mmap)
+                .with_threads(options.threads)
+                .with_start_offset(options.start_offset)
+                .with_end_offset(options.end_offset.unwrap_or(u64::MAX))
+                .with_binary_format(binary_format)
+                .with_sections(options.sections)
+                .with_archives(options.archives)
+                .with_max_archive_depth(options.max_archive_depth)
+                .with_max_member_size(options.max_member_size)
+                .with_decompression(decompression);
+            if let Some(max_length) = options.max_length {
+                config = config.with_max_length(max_length, max_length_policy);
+            }
+            extract_into(&config, &mut print_writer)
+        }
+        None => {
+            let path: &Path = Path::new(&file_paths[0]);
             if !path.is_file() {
                 eprintln!("File does not exists!");
                 exit(1);
             }
-            strings(
-                &FileConfig::new(path)
-                    .with_min_length(options.min_length)
-                    .with_encoding(encoding),
-            )
+            let mut config = FileConfig::new(path)
+                .with_min_length(options.min_length)
+                .with_null_terminated_only(options.null_terminated_only)
+                .with_encodings(encodings)
+                .with_character_set(character_set)
+                .with_unicode_scripts(unicode_scripts)
+                .with_mmap(options.mmap)
+                .with_threads(options.threads)
+                .with_start_offset(options.start_offset)
+                .with_end_offset(options.end_offset.unwrap_or(u64::MAX))
+                .with_binary_format(binary_format)
+                .with_sections(options.sections)
+                .with_archives(options.archives)
+                .with_max_archive_depth(options.max_archive_depth)
+                .with_max_member_size(options.max_member_size)
+                .with_decompression(decompression);
+            if let Some(max_length) = options.max_length {
+                config = config.with_max_length(max_length, max_length_policy);
+            }
+            extract_into(&config, &mut print_writer)
         }
+    };
+    if let Err(err) = result.and_then(|_| Ok(print_writer.output.flush()?)) {
+        eprintln!("{}", err);
+        exit(1);
     }
-    .expect("Something went wrong!");
-    for (string, offset) in extracted_strings {
-        if options.offset {
-            println!("{:10}: {}", offset, string);
-        } else {
-            println!("{}", string);
-        }
+    if print_writer.has_file_errors {
+        exit(1);
     }
 }
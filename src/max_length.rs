@@ -0,0 +1,69 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Marker appended to the text of a string cut by [`MaxLengthPolicy::Truncate`].
+pub(crate) const TRUNCATION_MARKER: &str = "...";
+
+/// What happens to a string longer than the maximum length.
+///
+/// Characters past the maximum length aren't kept in memory whatever the policy, so a huge run of
+/// printable bytes costs no more than a string of the maximum length.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum MaxLengthPolicy {
+    /// The string is skipped
+    Drop,
+    /// The text is cut at the maximum length and ends with `...`, offsets still span the whole
+    /// string
+    #[default]
+    Truncate,
+    /// The string is emitted as consecutive chunks of the maximum length, each with its own
+    /// offsets. Only the last chunk may be shorter than the minimum length, and only the last one
+    /// reports the terminator of the string
+    Split,
+}
+
+impl fmt::Display for MaxLengthPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug)]
+pub struct MaxLengthPolicyNotFoundError {
+    max_length_policy: String,
+}
+
+impl fmt::Display for MaxLengthPolicyNotFoundError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Max length policy not found: {:?}",
+            self.max_length_policy
+        )
+    }
+}
+
+impl MaxLengthPolicyNotFoundError {
+    fn new(max_length_policy: String) -> Self {
+        MaxLengthPolicyNotFoundError { max_length_policy }
+    }
+}
+
+impl Error for MaxLengthPolicyNotFoundError {}
+
+impl FromStr for MaxLengthPolicy {
+    type Err = MaxLengthPolicyNotFoundError;
+
+    fn from_str(max_length_policy: &str) -> Result<Self, Self::Err> {
+        let max_length_policy: &str = &max_length_policy.to_lowercase();
+        match max_length_policy {
+            "drop" => Ok(MaxLengthPolicy::Drop),
+            "truncate" => Ok(MaxLengthPolicy::Truncate),
+            "split" => Ok(MaxLengthPolicy::Split),
+            _ => Err(MaxLengthPolicyNotFoundError::new(
+                max_length_policy.to_owned(),
+            )),
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use std::io::{BufRead, Read};
+use std::thread;
+
+use crate::extracted_string::ExtractedString;
+use crate::strings::{new_strings_extractors, Config};
+use crate::strings_extractor::StringsExtractor;
+use crate::strings_writer::StringWriter;
+use crate::ErrorResult;
+
+/// A string along with the offset of the byte that ended it and the index of its encoding.
+type PositionedString = (u64, usize, ExtractedString);
+
+/// Extract strings with several threads, reading `threads` buffers at a time.
+///
+/// The first buffer of every batch is scanned by the extractors left by the previous batch, the
+/// others by new extractors which don't know what came before. Those are then stitched: the end
+/// of the previous buffer is carried over by replaying the start of the buffer until the carried
+/// extractor reaches the same state as a new one, from there on the strings of the new extractor
+/// are the right ones.
+pub(crate) fn extract_into_parallel<T: Config, W: StringWriter>(
+    strings_config: &T,
+    writer: &mut W,
+    threads: usize,
+) -> ErrorResult {
+    let mut reader = strings_config.get_reader()?;
+    let buffer_size = strings_config.get_buffer_size().max(1);
+    let mut strings_extractors = new_strings_extractors(strings_config);
+    let mut offset = strings_config.get_start_offset();
+    let mut owned_buffers;
+    loop {
+        let borrowed = reader.fill_buf()?.len() > buffer_size;
+        let buffers: Vec<(u64, &[u8])> = if borrowed {
+            // The reader hands out windows bigger than a buffer, e.g. a mapped file or bytes,
+            // which are scanned in place instead of being copied
+            reader
+                .fill_buf()?
+                .chunks(buffer_size)
+                .take(threads)
+                .scan(offset, |buffer_offset, buffer| {
+                    let positioned = (*buffer_offset, buffer);
+                    *buffer_offset += buffer.len() as u64;
+                    Some(positioned)
+                })
+                .collect()
+        } else {
+            owned_buffers = read_buffers(&mut reader, offset, buffer_size, threads)?;
+            owned_buffers
+                .iter()
+                .map(|(offset, buffer)| (*offset, buffer.as_slice()))
+                .collect()
+        };
+        let (last_offset, last_buffer) = match buffers.last() {
+            Some(last) => *last,
+            None => break,
+        };
+        let length = (last_offset - offset) as usize + last_buffer.len();
+        offset = last_offset + last_buffer.len() as u64;
+        let mut extracted_strings = vec![];
+        strings_extractors = scan_buffers(
+            strings_config,
+            strings_extractors,
+            &buffers,
+            &mut extracted_strings,
+        );
+        if borrowed {
+            reader.consume(length);
+        }
+        write_strings(writer, extracted_strings)?;
+    }
+    let mut extracted_strings = vec![];
+    for (i, strings_extractor) in strings_extractors.iter_mut().enumerate() {
+        strings_extractor.stop_consume();
+        take_strings(strings_extractor.as_mut(), i, &mut extracted_strings);
+    }
+    write_strings(writer, extracted_strings)
+}
+
+/// Reads up to `count` buffers of `buffer_size` bytes from a reader with smaller windows.
+fn read_buffers(
+    reader: &mut dyn BufRead,
+    mut offset: u64,
+    buffer_size: usize,
+    count: usize,
+) -> std::io::Result<Vec<(u64, Vec<u8>)>> {
+    let mut buffers = Vec::with_capacity(count);
+    while buffers.len() < count {
+        let mut buffer = Vec::with_capacity(buffer_size);
+        Read::take(&mut *reader, buffer_size as u64).read_to_end(&mut buffer)?;
+        if buffer.is_empty() {
+            break;
+        }
+        let length = buffer.len() as u64;
+        buffers.push((offset, buffer));
+        offset += length;
+    }
+    Ok(buffers)
+}
+
+/// Scans the buffers in parallel and returns the extractors at the end of the last one.
+fn scan_buffers<T: Config>(
+    strings_config: &T,
+    strings_extractors: Vec<Box<dyn StringsExtractor>>,
+    buffers: &[(u64, &[u8])],
+    extracted_strings: &mut Vec<PositionedString>,
+) -> Vec<Box<dyn StringsExtractor>> {
+    let mut buffers_extractors = vec![strings_extractors];
+    buffers_extractors.extend((1..buffers.len()).map(|_| new_strings_extractors(strings_config)));
+    let scanned: Vec<Vec<Box<dyn StringsExtractor>>> = thread::scope(|scope| {
+        let handles: Vec<_> = buffers_extractors
+            .into_iter()
+            .zip(buffers)
+            .map(|(mut strings_extractors, (offset, buffer))| {
+                scope.spawn(move || {
+                    strings_extractors
+                        .iter_mut()
+                        .for_each(|strings_extractor| strings_extractor.consume(*offset, buffer));
+                    strings_extractors
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|err| std::panic::resume_unwind(err))
+            })
+            .collect()
+    });
+    let mut scanned = scanned.into_iter();
+    let mut strings_extractors = scanned.next().unwrap_or_default();
+    for (i, strings_extractor) in strings_extractors.iter_mut().enumerate() {
+        take_strings(strings_extractor.as_mut(), i, extracted_strings);
+    }
+    for (buffer_extractors, (offset, buffer)) in scanned.zip(&buffers[1..]) {
+        strings_extractors = strings_extractors
+            .into_iter()
+            .zip(buffer_extractors)
+            .zip(new_strings_extractors(strings_config))
+            .enumerate()
+            .map(
+                |(i, ((strings_extractor, buffer_extractor), replay_extractor))| {
+                    stitch(
+                        strings_extractor,
+                        buffer_extractor,
+                        replay_extractor,
+                        *offset,
+                        buffer,
+                        i,
+                        extracted_strings,
+                    )
+                },
+            )
+            .collect();
+    }
+    strings_extractors
+}
+
+/// Carries `strings_extractor` over the start of the buffer until it's in the same state as
+/// `replay_extractor`, a new extractor like the one that scanned the buffer. Returns the
+/// extractor at the end of the buffer.
+fn stitch(
+    mut strings_extractor: Box<dyn StringsExtractor>,
+    mut buffer_extractor: Box<dyn StringsExtractor>,
+    mut replay_extractor: Box<dyn StringsExtractor>,
+    offset: u64,
+    buffer: &[u8],
+    index: usize,
+    extracted_strings: &mut Vec<PositionedString>,
+) -> Box<dyn StringsExtractor> {
+    let mut synchronized_position = offset;
+    if !strings_extractor.has_same_state(replay_extractor.as_ref()) {
+        let synchronized = buffer.iter().enumerate().find_map(|(i, c)| {
+            let position = offset + i as u64;
+            strings_extractor.consume(position, std::slice::from_ref(c));
+            replay_extractor.consume(position, std::slice::from_ref(c));
+            Some(position + 1)
+                .filter(|_| strings_extractor.has_same_state(replay_extractor.as_ref()))
+        });
+        take_strings(strings_extractor.as_mut(), index, extracted_strings);
+        match synchronized {
+            Some(position) => synchronized_position = position,
+            // The carried extractor went through the whole buffer
+            None => return strings_extractor,
+        }
+    }
+    extracted_strings.extend(
+        buffer_extractor
+            .take_strings()
+            .into_iter()
+            .filter(|(position, _)| *position >= synchronized_position)
+            .map(|(position, extracted_string)| (position, index, extracted_string)),
+    );
+    buffer_extractor
+}
+
+fn take_strings(
+    strings_extractor: &mut dyn StringsExtractor,
+    index: usize,
+    extracted_strings: &mut Vec<PositionedString>,
+) {
+    extracted_strings.extend(
+        strings_extractor
+            .take_strings()
+            .into_iter()
+            .map(|(position, extracted_string)| (position, index, extracted_string)),
+    );
+}
+
+/// Writes the strings in the order they ended, like a single thread would.
+fn write_strings<W: StringWriter>(
+    writer: &mut W,
+    mut extracted_strings: Vec<PositionedString>,
+) -> ErrorResult {
+    extracted_strings.sort_by_key(|(position, index, _)| (*position, *index));
+    extracted_strings
+        .into_iter()
+        .try_for_each(|(_, _, extracted_string)| writer.write_string(extracted_string))
+}
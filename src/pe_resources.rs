@@ -0,0 +1,279 @@
+use object::pe;
+use object::read::pe::{ImageNtHeaders, PeFile, ResourceDirectory, ResourceNameOrId};
+use object::{LittleEndian as LE, ReadRef};
+
+use crate::binary_formats::{ParsedRegions, ParsedString, Region};
+use crate::encodings::Encoding;
+
+/// Regions of the resources of a PE file, which replace the data of every resource. String tables
+/// and version info are parsed into their strings, a malformed directory is left to be scanned as
+/// plain data.
+pub(crate) fn find_resources<'data, Pe: ImageNtHeaders, R: ReadRef<'data>>(
+    file: &PeFile<'data, Pe, R>,
+    data: R,
+) -> ParsedRegions {
+    parse_resources(file, data).unwrap_or_default()
+}
+
+fn parse_resources<'data, Pe: ImageNtHeaders, R: ReadRef<'data>>(
+    file: &PeFile<'data, Pe, R>,
+    data: R,
+) -> object::Result<ParsedRegions> {
+    let section_table = file.section_table();
+    let directory = match file
+        .data_directories()
+        .resource_directory(data, &section_table)?
+    {
+        Some(directory) => directory,
+        None => return Ok(ParsedRegions::default()),
+    };
+    let mut resources = ParsedRegions::default();
+    for type_entry in directory.root()?.entries {
+        let type_id = type_entry.name_or_id().id();
+        let type_name = match type_id {
+            Some(type_id) => resource_type_name(type_id),
+            None => resource_name(type_entry.name_or_id(), directory)?,
+        };
+        let names = match type_entry.data(directory)?.table() {
+            Some(names) => names,
+            None => continue,
+        };
+        for name_entry in names.entries {
+            let name = resource_name(name_entry.name_or_id(), directory)?;
+            let languages = match name_entry.data(directory)?.table() {
+                Some(languages) => languages,
+                None => continue,
+            };
+            for language_entry in languages.entries {
+                let language = resource_name(language_entry.name_or_id(), directory)?;
+                let data_entry = match language_entry.data(directory)?.data() {
+                    Some(data_entry) => data_entry,
+                    None => continue,
+                };
+                let rva = data_entry.offset_to_data.get(LE);
+                let (offset, available_length) = match section_table.pe_file_range_at(rva) {
+                    Some(range) => range,
+                    None => continue,
+                };
+                let offset = u64::from(offset);
+                let length = u64::from(data_entry.size.get(LE).min(available_length));
+                let entry_data = match data.read_bytes_at(offset, length) {
+                    Ok(entry_data) => entry_data,
+                    Err(_) => continue,
+                };
+                let path = format!("{}/{}/{}", type_name, name, language);
+                resources.ranges.push((offset, length));
+                match type_id {
+                    Some(pe::RT_STRING) => resources.regions.extend(string_table_regions(
+                        entry_data,
+                        offset,
+                        &path,
+                        name_entry.name_or_id().id(),
+                    )),
+                    Some(pe::RT_VERSION) => resources
+                        .regions
+                        .extend(version_info_regions(entry_data, offset, &path)),
+                    _ => {
+                        let mut region = Region::new(offset, length);
+                        region.resource = Some(path);
+                        resources.regions.push(region);
+                    }
+                }
+            }
+        }
+    }
+    Ok(resources)
+}
+
+fn resource_name(
+    name_or_id: ResourceNameOrId,
+    directory: ResourceDirectory<'_>,
+) -> object::Result<String> {
+    match name_or_id {
+        ResourceNameOrId::Name(name) => name.to_string_lossy(directory),
+        ResourceNameOrId::Id(id) => Ok(id.to_string()),
+    }
+}
+
+fn resource_type_name(type_id: u16) -> String {
+    let type_name = match type_id {
+        pe::RT_CURSOR => "RT_CURSOR",
+        pe::RT_BITMAP => "RT_BITMAP",
+        pe::RT_ICON => "RT_ICON",
+        pe::RT_MENU => "RT_MENU",
+        pe::RT_DIALOG => "RT_DIALOG",
+        pe::RT_STRING => "RT_STRING",
+        pe::RT_FONTDIR => "RT_FONTDIR",
+        pe::RT_FONT => "RT_FONT",
+        pe::RT_ACCELERATOR => "RT_ACCELERATOR",
+        pe::RT_RCDATA => "RT_RCDATA",
+        pe::RT_MESSAGETABLE => "RT_MESSAGETABLE",
+        pe::RT_GROUP_CURSOR => "RT_GROUP_CURSOR",
+        pe::RT_GROUP_ICON => "RT_GROUP_ICON",
+        pe::RT_VERSION => "RT_VERSION",
+        pe::RT_DLGINCLUDE => "RT_DLGINCLUDE",
+        pe::RT_PLUGPLAY => "RT_PLUGPLAY",
+        pe::RT_VXD => "RT_VXD",
+        pe::RT_ANICURSOR => "RT_ANICURSOR",
+        pe::RT_ANIICON => "RT_ANIICON",
+        pe::RT_HTML => "RT_HTML",
+        pe::RT_MANIFEST => "RT_MANIFEST",
+        _ => return type_id.to_string(),
+    };
+    String::from(type_name)
+}
+
+fn read_u16(data: &[u8], position: usize) -> Option<u16> {
+    let bytes = data.get(position..position.checked_add(2)?)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+/// Decodes utf-16 code units up to a null or `end`, returns the text, the offset after its last
+/// code unit and whether it is null terminated.
+fn read_utf16(data: &[u8], start: usize, end: usize) -> (String, usize, bool) {
+    let mut position = start;
+    let mut units = vec![];
+    while position + 2 <= end {
+        match read_u16(data, position) {
+            Some(0) => {
+                let text = char::decode_utf16(units)
+                    .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                    .collect();
+                return (text, position, true);
+            }
+            Some(unit) => units.push(unit),
+            None => break,
+        }
+        position += 2;
+    }
+    let text = char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect();
+    (text, position, false)
+}
+
+fn parsed_region(
+    offset: u64,
+    length: u64,
+    resource: String,
+    text: String,
+    null_terminated: bool,
+) -> Region {
+    let mut region = Region::new(offset, length);
+    region.resource = Some(resource);
+    region.parsed_string = Some(ParsedString {
+        text,
+        encoding: Encoding::UTF16LE,
+        null_terminated: Some(null_terminated),
+    });
+    region
+}
+
+/// Strings of a `RT_STRING` block, 16 length prefixed strings whose ids follow the block id.
+fn string_table_regions(
+    data: &[u8],
+    offset: u64,
+    path: &str,
+    block_id: Option<u16>,
+) -> Vec<Region> {
+    let mut regions = vec![];
+    let mut position = 0;
+    for index in 0..16 {
+        let length = match read_u16(data, position) {
+            Some(length) => usize::from(length),
+            None => break,
+        };
+        let start = position + 2;
+        let end = start + length * 2;
+        if end > data.len() {
+            break;
+        }
+        if length > 0 {
+            let (text, _, _) = read_utf16(data, start, end);
+            let string_id = match block_id {
+                Some(block_id) => (u32::from(block_id).saturating_sub(1) * 16 + index).to_string(),
+                None => index.to_string(),
+            };
+            regions.push(parsed_region(
+                offset + start as u64,
+                (end - start) as u64,
+                format!("{}/{}", path, string_id),
+                text,
+                false,
+            ));
+        }
+        position = end;
+    }
+    regions
+}
+
+/// A `VS_VERSIONINFO` block: its key, the range of its value and the range of its children.
+struct VersionBlock {
+    key: String,
+    value: (usize, usize),
+    children: (usize, usize),
+}
+
+fn align_4(position: usize) -> usize {
+    position.saturating_add(3) & !3
+}
+
+fn version_blocks(data: &[u8], mut start: usize, end: usize) -> Vec<VersionBlock> {
+    let mut blocks = vec![];
+    while let (Some(length), Some(value_length), Some(value_type)) = (
+        read_u16(data, start),
+        read_u16(data, start + 2),
+        read_u16(data, start + 4),
+    ) {
+        let block_end = (start + usize::from(length)).min(end);
+        if length < 6 || block_end <= start + 6 {
+            break;
+        }
+        let (key, key_end, _) = read_utf16(data, start + 6, block_end);
+        let value_start = align_4(key_end + 2).min(block_end);
+        // Text values are measured in code units, binary values in bytes
+        let value_length = match value_type {
+            1 => usize::from(value_length) * 2,
+            _ => usize::from(value_length),
+        };
+        let value_end = (value_start + value_length).min(block_end);
+        blocks.push(VersionBlock {
+            key,
+            value: (value_start, value_end),
+            children: (align_4(value_end).min(block_end), block_end),
+        });
+        start = align_4(block_end);
+    }
+    blocks
+}
+
+/// Strings of the `StringFileInfo` of a `VS_VERSIONINFO` resource, such as `CompanyName`.
+fn version_info_regions(data: &[u8], offset: u64, path: &str) -> Vec<Region> {
+    let mut regions = vec![];
+    let root = match version_blocks(data, 0, data.len()).into_iter().next() {
+        Some(root) => root,
+        None => return regions,
+    };
+    for file_info in version_blocks(data, root.children.0, root.children.1) {
+        if file_info.key != "StringFileInfo" {
+            continue;
+        }
+        for table in version_blocks(data, file_info.children.0, file_info.children.1) {
+            for string in version_blocks(data, table.children.0, table.children.1) {
+                let (value_start, value_end) = string.value;
+                let (text, text_end, null_terminated) = read_utf16(data, value_start, value_end);
+                if text.is_empty() {
+                    continue;
+                }
+                regions.push(parsed_region(
+                    offset + value_start as u64,
+                    (text_end - value_start) as u64,
+                    format!("{}/{}/{}", path, table.key, string.key),
+                    text,
+                    null_terminated,
+                ));
+            }
+        }
+    }
+    regions
+}
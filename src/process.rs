@@ -0,0 +1,116 @@
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::os::unix::fs::FileExt;
+
+use crate::binary_formats::Region;
+
+/// Granularity at which memory that can't be read is probed.
+const PAGE_SIZE: u64 = 4096;
+
+/// Readable mappings of a process, as regions whose offsets are their addresses in
+/// `/proc/<pid>/mem`. Mappings are split around the pages that can't be read through it, so that
+/// the strings before them end like at the end of the input.
+pub(crate) fn find_mappings(pid: u32) -> Result<Vec<Region>, Box<dyn Error>> {
+    let maps = fs::read_to_string(format!("/proc/{}/maps", pid))?;
+    let memory = File::open(format!("/proc/{}/mem", pid))?;
+    let mut regions = vec![];
+    for line in maps.lines() {
+        // address perms offset dev inode pathname, the pathname is padded and may hold spaces
+        let mut fields = line.splitn(6, ' ');
+        let (addresses, permissions) = match (fields.next(), fields.next()) {
+            (Some(addresses), Some(permissions)) => (addresses, permissions),
+            _ => return Err(format!("Invalid mapping: {:?}", line).into()),
+        };
+        let (start, end) = addresses
+            .split_once('-')
+            .and_then(|(start, end)| {
+                let start = u64::from_str_radix(start, 16).ok()?;
+                let end = u64::from_str_radix(end, 16).ok()?;
+                Some((start, end)).filter(|_| start <= end)
+            })
+            .ok_or_else(|| format!("Invalid mapping: {:?}", line))?;
+        if !permissions.starts_with('r') || start == end {
+            continue;
+        }
+        let pathname = fields.nth(3).unwrap_or_default().trim_start();
+        let mut region = Region::new(start, end - start);
+        region.virtual_address = Some(start);
+        region.mapping = Some(pathname.to_owned()).filter(|pathname| !pathname.is_empty());
+        regions.extend(readable_parts(&memory, &region));
+    }
+    Ok(regions)
+}
+
+/// Parts of a region made of pages that can be read, probed by reading a byte of every page.
+fn readable_parts(memory: &File, region: &Region) -> Vec<Region> {
+    let end = region.offset + region.length;
+    let mut parts = vec![];
+    let mut part_start = None;
+    let mut page = region.offset;
+    while page < end {
+        match (is_readable(memory, page), part_start) {
+            (true, None) => part_start = Some(page),
+            (false, Some(start)) => {
+                parts.extend(region.clip(start, page));
+                part_start = None;
+            }
+            _ => {}
+        }
+        page = (page / PAGE_SIZE + 1).saturating_mul(PAGE_SIZE).min(end);
+    }
+    parts.extend(part_start.and_then(|start| region.clip(start, end)));
+    parts
+}
+
+fn is_readable(memory: &File, address: u64) -> bool {
+    let mut byte = [0];
+    loop {
+        match memory.read_at(&mut byte, address) {
+            Ok(length) => return length == 1,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(_) => return false,
+        }
+    }
+}
+
+/// Reader over the memory of a process from an address, which ends at a page that can't be read
+/// rather than making up its bytes, e.g. a page unmapped since the mappings were listed.
+pub(crate) struct ProcessMemoryReader {
+    memory: File,
+    position: u64,
+    end: u64,
+}
+
+impl ProcessMemoryReader {
+    pub(crate) fn new(pid: u32, start_address: u64, length: Option<u64>) -> io::Result<Self> {
+        Ok(ProcessMemoryReader {
+            memory: File::open(format!("/proc/{}/mem", pid))?,
+            position: start_address,
+            end: length.map_or(u64::MAX, |length| start_address.saturating_add(length)),
+        })
+    }
+}
+
+impl Read for ProcessMemoryReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let length = (buf.len() as u64).min(self.end - self.position) as usize;
+        if length == 0 {
+            return Ok(0);
+        }
+        let buf = &mut buf[..length];
+        let length = loop {
+            match self.memory.read_at(buf, self.position) {
+                Ok(length) => break length,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                // Guard pages, pages of devices and pages unmapped since the maps were read
+                Err(_) => {
+                    self.end = self.position;
+                    break 0;
+                }
+            }
+        };
+        self.position += length as u64;
+        Ok(length)
+    }
+}
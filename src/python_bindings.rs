@@ -1,18 +1,31 @@
 use pyo3::create_exception;
-use pyo3::exceptions::PyException;
+use pyo3::exceptions::{PyException, PyTypeError};
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 use std::error::Error;
 use std::path::PathBuf;
 use std::str::FromStr;
 
+use crate::binary_formats::BinaryFormatNotFoundError;
 use crate::encodings::EncodingNotFoundError;
+use crate::max_length::MaxLengthPolicyNotFoundError;
+use crate::unicode_scripts::UnicodeScriptNotFoundError;
 use crate::{
-    dump_strings as r_dump_strings, strings as r_strings, BytesConfig as RustBytesConfig,
-    Encoding as RustEncoding, ErrorResult, FileConfig as RustFileConfig,
+    dump_strings as r_dump_strings, extract_strings as r_extract_strings, strings as r_strings,
+    BinaryFormat as RustBinaryFormat, BytesConfig as RustBytesConfig, Encoding as RustEncoding,
+    ExtractedString as RustExtractedString, FileConfig as RustFileConfig,
+    MaxLengthPolicy as RustMaxLengthPolicy, UnicodeScript as RustUnicodeScript,
 };
 
 create_exception!(pystrings, StringsException, PyException);
 create_exception!(pystrings, EncodingNotFoundException, StringsException);
+create_exception!(pystrings, UnicodeScriptNotFoundException, StringsException);
+create_exception!(pystrings, BinaryFormatNotFoundException, StringsException);
+create_exception!(
+    pystrings,
+    MaxLengthPolicyNotFoundException,
+    StringsException
+);
 
 impl From<EncodingNotFoundError> for PyErr {
     fn from(err: EncodingNotFoundError) -> PyErr {
@@ -20,66 +33,289 @@ impl From<EncodingNotFoundError> for PyErr {
     }
 }
 
+impl From<UnicodeScriptNotFoundError> for PyErr {
+    fn from(err: UnicodeScriptNotFoundError) -> PyErr {
+        UnicodeScriptNotFoundException::new_err(format!("{}", err))
+    }
+}
+
+impl From<BinaryFormatNotFoundError> for PyErr {
+    fn from(err: BinaryFormatNotFoundError) -> PyErr {
+        BinaryFormatNotFoundException::new_err(format!("{}", err))
+    }
+}
+
+impl From<MaxLengthPolicyNotFoundError> for PyErr {
+    fn from(err: MaxLengthPolicyNotFoundError) -> PyErr {
+        MaxLengthPolicyNotFoundException::new_err(format!("{}", err))
+    }
+}
+
+/// A string found in the input, along with its location and encoding. Unlike the Rust struct, it has
+/// no `file_name`, `member` or `compression`, as the functions scan a single file or bytes as is.
+#[pyclass(name = "ExtractedString", get_all, frozen)]
+struct ExtractedString {
+    text: String,
+    offset: u64,
+    end_offset: u64,
+    byte_length: u64,
+    encoding: String,
+    null_terminated: Option<bool>,
+    section: Option<String>,
+    virtual_address: Option<u64>,
+    relative_virtual_address: Option<u64>,
+    resource: Option<String>,
+    architecture: Option<String>,
+    cfstring_address: Option<u64>,
+    mapping: Option<String>,
+}
+
+#[pymethods]
+impl ExtractedString {
+    fn __repr__(&self) -> String {
+        format!(
+            "ExtractedString(text={:?}, offset={}, end_offset={}, byte_length={}, encoding={:?})",
+            self.text, self.offset, self.end_offset, self.byte_length, self.encoding
+        )
+    }
+}
+
+impl From<RustExtractedString> for ExtractedString {
+    fn from(extracted_string: RustExtractedString) -> Self {
+        ExtractedString {
+            text: extracted_string.text,
+            offset: extracted_string.offset,
+            end_offset: extracted_string.end_offset,
+            byte_length: extracted_string.byte_length,
+            encoding: extracted_string.encoding.to_string(),
+            null_terminated: extracted_string.null_terminated,
+            section: extracted_string.section,
+            virtual_address: extracted_string.virtual_address,
+            relative_virtual_address: extracted_string.relative_virtual_address,
+            resource: extracted_string.resource,
+            architecture: extracted_string.architecture,
+            cfstring_address: extracted_string.cfstring_address,
+            mapping: extracted_string.mapping,
+        }
+    }
+}
+
+/// Options shared by every function, turned into the matching Rust config.
+#[derive(Default)]
+struct StringsOptions {
+    file_path: Option<PathBuf>,
+    bytes: Option<Vec<u8>>,
+    min_length: usize,
+    max_length: Option<usize>,
+    max_length_policy: String,
+    null_terminated_only: bool,
+    encodings: Vec<String>,
+    buffer_size: usize,
+    unicode_scripts: Vec<String>,
+    mmap: bool,
+    threads: usize,
+    start_offset: u64,
+    end_offset: Option<u64>,
+    binary_format: String,
+    sections: Vec<String>,
+}
+
+impl StringsOptions {
+    /// Options of the input, along with the keyword arguments given to the function `name`.
+    fn new(
+        name: &str,
+        file_path: Option<PathBuf>,
+        bytes: Option<Vec<u8>>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Self> {
+        let mut options = StringsOptions {
+            file_path,
+            bytes,
+            min_length: 3,
+            max_length_policy: String::from("truncate"),
+            encodings: vec![String::from("ascii")],
+            buffer_size: 1024 * 1024,
+            threads: 1,
+            binary_format: String::from("raw"),
+            ..StringsOptions::default()
+        };
+        for (key, value) in kwargs.into_iter().flatten() {
+            let key: String = key.extract()?;
+            match key.as_str() {
+                "min_length" => options.min_length = value.extract()?,
+                "max_length" => options.max_length = value.extract()?,
+                "max_length_policy" => options.max_length_policy = value.extract()?,
+                "null_terminated_only" => options.null_terminated_only = value.extract()?,
+                "encodings" => options.encodings = value.extract()?,
+                "buffer_size" => options.buffer_size = value.extract()?,
+                "unicode_scripts" => options.unicode_scripts = value.extract()?,
+                "mmap" => options.mmap = value.extract()?,
+                "threads" => options.threads = value.extract()?,
+                "start_offset" => options.start_offset = value.extract()?,
+                "end_offset" => options.end_offset = value.extract()?,
+                "binary_format" => options.binary_format = value.extract()?,
+                "sections" => options.sections = value.extract()?,
+                _ => {
+                    return Err(PyTypeError::new_err(format!(
+                        "{}() got an unexpected keyword argument '{}'",
+                        name, key
+                    )))
+                }
+            }
+        }
+        Ok(options)
+    }
+
+    /// Runs `file_func` or `bytes_func` on the config built from the options.
+    fn run<T, F, B>(self, file_func: F, bytes_func: B) -> PyResult<T>
+    where
+        F: FnOnce(&RustFileConfig) -> Result<T, Box<dyn Error>>,
+        B: FnOnce(&RustBytesConfig) -> Result<T, Box<dyn Error>>,
+    {
+        if self.file_path.is_some() && self.bytes.is_some() {
+            return Err(StringsException::new_err(
+                "You can't specify file_path and bytes",
+            ));
+        }
+        let encodings = self
+            .encodings
+            .iter()
+            .map(|e| RustEncoding::from_str(e))
+            .collect::<Result<Vec<RustEncoding>, _>>()?;
+        let unicode_scripts = self
+            .unicode_scripts
+            .iter()
+            .map(|s| RustUnicodeScript::from_str(s))
+            .collect::<Result<Vec<RustUnicodeScript>, _>>()?;
+        let binary_format = RustBinaryFormat::from_str(&self.binary_format)?;
+        let max_length_policy = RustMaxLengthPolicy::from_str(&self.max_length_policy)?;
+        let result: Result<T, Box<dyn Error>>;
+        if let Some(file_path) = self.file_path {
+            let mut strings_config = RustFileConfig::new(&file_path)
+                .with_min_length(self.min_length)
+                .with_null_terminated_only(self.null_terminated_only)
+                .with_encodings(encodings)
+                .with_unicode_scripts(unicode_scripts)
+                .with_buffer_size(self.buffer_size)
+                .with_mmap(self.mmap)
+                .with_threads(self.threads)
+                .with_start_offset(self.start_offset)
+                .with_end_offset(self.end_offset.unwrap_or(u64::MAX))
+                .with_binary_format(binary_format)
+                .with_sections(self.sections);
+            if let Some(max_length) = self.max_length {
+                strings_config = strings_config.with_max_length(max_length, max_length_policy);
+            }
+            result = file_func(&strings_config);
+        } else if let Some(bytes) = self.bytes {
+            let mut strings_config = RustBytesConfig::new(bytes)
+                .with_min_length(self.min_length)
+                .with_null_terminated_only(self.null_terminated_only)
+                .with_encodings(encodings)
+                .with_unicode_scripts(unicode_scripts)
+                .with_threads(self.threads)
+                .with_start_offset(self.start_offset)
+                .with_end_offset(self.end_offset.unwrap_or(u64::MAX))
+                .with_binary_format(binary_format)
+                .with_sections(self.sections);
+            if let Some(max_length) = self.max_length {
+                strings_config = strings_config.with_max_length(max_length, max_length_policy);
+            }
+            result = bytes_func(&strings_config);
+        } else {
+            return Err(StringsException::new_err(
+                "You must specify file_path or bytes",
+            ));
+        }
+        result.map_err(|error_message| StringsException::new_err(format!("{}", error_message)))
+    }
+}
+
 /// Extract strings from binary file or bytes.
 /// :param file_path: path to file (can't be with bytes option)
 /// :param bytes: bytes (can't be with file_path option)
 /// :param min_length: strings minimum length
+/// :param max_length: strings maximum length, longer strings are handled according to max_length_policy (default is no maximum)
+/// :param max_length_policy: what happens to strings longer than max_length, "drop", "truncate" to cut them and end them with "..." or "split" into consecutive chunks (default is "truncate")
+/// :param null_terminated_only: extract only the strings ended by a null character of their encoding, e.g. 0x00 in ascii or 0x0000 in utf-16, whose null_terminated is True (default is False)
 /// :param encoding: strings encoding (default is ["ascii"])
 /// :param buffer_size: the buffer size to read the file (relevant only to file_path option)
+/// :param unicode_scripts: unicode scripts allowed in utf-16 strings besides ascii (default is [])
+/// :param mmap: map the file into memory instead of reading it with a buffer, only faster with several threads (relevant only to file_path option)
+/// :param threads: number of threads scanning the input, 0 uses every core (default is 1)
+/// :param start_offset: offset to start scanning from, offsets stay relative to the whole input
+/// :param end_offset: offset to stop scanning at (default is the end of the input)
+/// :param binary_format: parse the input as an executable and scan its sections only, "raw", "elf", "pe", "macho" or "core" for ELF core dumps, whose sections are the paths of mapped files (default is "raw")
+/// :param sections: sections to scan when parsing an executable, e.g. [".rodata"] or ["__TEXT,__cstring"] (default is every section)
 /// :return: list of tuples of string and offset
 /// :raises: raise StringsException if there is any error during string extraction
 ///          raise EncodingNotFoundException if the function got an unsupported encondings
+///          raise UnicodeScriptNotFoundException if the function got an unsupported unicode scripts
+///          raise BinaryFormatNotFoundException if the function got an unsupported binary format
+///          raise MaxLengthPolicyNotFoundException if the function got an unsupported max length policy
 #[pyfunction()]
 #[pyo3(signature=(
     file_path = None,
     bytes = None,
-    min_length = 3,
-    encodings = vec![String::from("ascii")],
-    buffer_size = 1024 * 1024
+    **options
 ))]
 #[pyo3(
-    text_signature = "(file_path: Optional[Union[str, Path]] = None, bytes: Optional[bytes] = None, min_length: int = 3, encoding: List[str] = [\"ascii\"], buffer_size: int = 1024 * 1024) -> List[Tuple[str, int]]"
+    text_signature = "(file_path: Optional[Union[str, Path]] = None, bytes: Optional[bytes] = None, *, min_length: int = 3, max_length: Optional[int] = None, max_length_policy: str = \"truncate\", null_terminated_only: bool = False, encoding: List[str] = [\"ascii\"], buffer_size: int = 1024 * 1024, unicode_scripts: List[str] = [], mmap: bool = False, threads: int = 1, start_offset: int = 0, end_offset: Optional[int] = None, binary_format: str = \"raw\", sections: List[str] = []) -> List[Tuple[str, int]]"
 )]
 fn strings(
     py: Python<'_>,
     file_path: Option<PathBuf>,
     bytes: Option<Vec<u8>>,
-    min_length: usize,
-    encodings: Vec<String>,
-    buffer_size: usize,
+    options: Option<&Bound<'_, PyDict>>,
 ) -> PyResult<Vec<(String, u64)>> {
-    py.detach(|| {
-        if matches!(file_path, Some(_)) && matches!(bytes, Some(_)) {
-            return Err(StringsException::new_err(
-                "You can't specify file_path and bytes",
-            ));
-        }
-        let encodings = encodings
-            .iter()
-            .map(|e| RustEncoding::from_str(e))
-            .collect::<Result<Vec<RustEncoding>, _>>()?;
-        let result: Result<Vec<(String, u64)>, Box<dyn Error>>;
-        if let Some(file_path) = file_path {
-            let strings_config = RustFileConfig::new(&file_path)
-                .with_min_length(min_length)
-                .with_encodings(encodings)
-                .with_buffer_size(buffer_size);
-            result = r_strings(&strings_config);
-        } else if let Some(bytes) = bytes {
-            let strings_config = RustBytesConfig::new(bytes)
-                .with_min_length(min_length)
-                .with_encodings(encodings);
-            result = r_strings(&strings_config);
-        } else {
-            return Err(StringsException::new_err(
-                "You must specify file_path or bytes",
-            ));
-        }
-        if let Err(error_message) = result {
-            return Err(StringsException::new_err(format!("{}", error_message)));
-        }
-        Ok(result.unwrap())
-    })
+    let options = StringsOptions::new("strings", file_path, bytes, options)?;
+    py.detach(|| options.run(|config| r_strings(config), r_strings))
+}
+
+/// Extract strings from binary file or bytes, along with their location and encoding.
+/// :param file_path: path to file (can't be with bytes option)
+/// :param bytes: bytes (can't be with file_path option)
+/// :param min_length: strings minimum length
+/// :param max_length: strings maximum length, longer strings are handled according to max_length_policy (default is no maximum)
+/// :param max_length_policy: what happens to strings longer than max_length, "drop", "truncate" to cut them and end them with "..." or "split" into consecutive chunks (default is "truncate")
+/// :param null_terminated_only: extract only the strings ended by a null character of their encoding, e.g. 0x00 in ascii or 0x0000 in utf-16, whose null_terminated is True (default is False)
+/// :param encoding: strings encoding (default is ["ascii"])
+/// :param buffer_size: the buffer size to read the file (relevant only to file_path option)
+/// :param unicode_scripts: unicode scripts allowed in utf-16 strings besides ascii (default is [])
+/// :param mmap: map the file into memory instead of reading it with a buffer, only faster with several threads (relevant only to file_path option)
+/// :param threads: number of threads scanning the input, 0 uses every core (default is 1)
+/// :param start_offset: offset to start scanning from, offsets stay relative to the whole input
+/// :param end_offset: offset to stop scanning at (default is the end of the input)
+/// :param binary_format: parse the input as an executable and scan its sections only, "raw", "elf", "pe", "macho" or "core" for ELF core dumps, whose sections are the paths of mapped files (default is "raw")
+/// :param sections: sections to scan when parsing an executable, e.g. [".rodata"] or ["__TEXT,__cstring"] (default is every section)
+/// :return: list of ExtractedString
+/// :raises: raise StringsException if there is any error during string extraction
+///          raise EncodingNotFoundException if the function got an unsupported encondings
+///          raise UnicodeScriptNotFoundException if the function got an unsupported unicode scripts
+///          raise BinaryFormatNotFoundException if the function got an unsupported binary format
+///          raise MaxLengthPolicyNotFoundException if the function got an unsupported max length policy
+#[pyfunction()]
+#[pyo3(signature=(
+    file_path = None,
+    bytes = None,
+    **options
+))]
+#[pyo3(
+    text_signature = "(file_path: Optional[Union[str, Path]] = None, bytes: Optional[bytes] = None, *, min_length: int = 3, max_length: Optional[int] = None, max_length_policy: str = \"truncate\", null_terminated_only: bool = False, encoding: List[str] = [\"ascii\"], buffer_size: int = 1024 * 1024, unicode_scripts: List[str] = [], mmap: bool = False, threads: int = 1, start_offset: int = 0, end_offset: Optional[int] = None, binary_format: str = \"raw\", sections: List[str] = []) -> List[ExtractedString]"
+)]
+fn extract_strings(
+    py: Python<'_>,
+    file_path: Option<PathBuf>,
+    bytes: Option<Vec<u8>>,
+    options: Option<&Bound<'_, PyDict>>,
+) -> PyResult<Vec<ExtractedString>> {
+    let options = StringsOptions::new("extract_strings", file_path, bytes, options)?;
+    let extracted_strings =
+        py.detach(|| options.run(|config| r_extract_strings(config), r_extract_strings))?;
+    Ok(extracted_strings
+        .into_iter()
+        .map(ExtractedString::from)
+        .collect())
 }
 
 /// Dump strings from binary file or bytes to json file.
@@ -87,63 +323,47 @@ fn strings(
 /// :param file_path: path to file (can't be with bytes option)
 /// :param bytes: bytes (can't be with file_path option)
 /// :param min_length: strings minimum length
+/// :param max_length: strings maximum length, longer strings are handled according to max_length_policy (default is no maximum)
+/// :param max_length_policy: what happens to strings longer than max_length, "drop", "truncate" to cut them and end them with "..." or "split" into consecutive chunks (default is "truncate")
+/// :param null_terminated_only: extract only the strings ended by a null character of their encoding, e.g. 0x00 in ascii or 0x0000 in utf-16, whose null_terminated is True (default is False)
 /// :param encoding: strings encoding (default is ["ascii"])
 /// :param buffer_size: the buffer size to read the file (relevant only to file_path option)
+/// :param unicode_scripts: unicode scripts allowed in utf-16 strings besides ascii (default is [])
+/// :param mmap: map the file into memory instead of reading it with a buffer, only faster with several threads (relevant only to file_path option)
+/// :param threads: number of threads scanning the input, 0 uses every core (default is 1)
+/// :param start_offset: offset to start scanning from, offsets stay relative to the whole input
+/// :param end_offset: offset to stop scanning at (default is the end of the input)
+/// :param binary_format: parse the input as an executable and scan its sections only, "raw", "elf", "pe", "macho" or "core" for ELF core dumps, whose sections are the paths of mapped files (default is "raw")
+/// :param sections: sections to scan when parsing an executable, e.g. [".rodata"] or ["__TEXT,__cstring"] (default is every section)
 /// :return: list of tuples of string and offset
 /// :raises: raise StringsException if there is any error during string extraction
 ///          raise EncodingNotFoundException if the function got an unsupported encondings
+///          raise UnicodeScriptNotFoundException if the function got an unsupported unicode scripts
+///          raise BinaryFormatNotFoundException if the function got an unsupported binary format
+///          raise MaxLengthPolicyNotFoundException if the function got an unsupported max length policy
 #[pyfunction()]
 #[pyo3(signature=(
     output_file,
     file_path = None,
     bytes = None,
-    min_length = 3,
-    encodings = vec![String::from("ascii")],
-    buffer_size = 1024 * 1024
+    **options
 ))]
 #[pyo3(
-    text_signature = "(output_file: str, file_path: Optional[Union[str, Path]] = None, bytes: Optional[bytes] = None, min_length: int = 3, encoding: List[str] = [\"ascii\"], buffer_size: int = 1024 * 1024) -> None"
+    text_signature = "(output_file: str, file_path: Optional[Union[str, Path]] = None, bytes: Optional[bytes] = None, *, min_length: int = 3, max_length: Optional[int] = None, max_length_policy: str = \"truncate\", null_terminated_only: bool = False, encoding: List[str] = [\"ascii\"], buffer_size: int = 1024 * 1024, unicode_scripts: List[str] = [], mmap: bool = False, threads: int = 1, start_offset: int = 0, end_offset: Optional[int] = None, binary_format: str = \"raw\", sections: List[str] = []) -> None"
 )]
 fn dump_strings(
     py: Python<'_>,
     output_file: PathBuf,
     file_path: Option<PathBuf>,
     bytes: Option<Vec<u8>>,
-    min_length: usize,
-    encodings: Vec<String>,
-    buffer_size: usize,
+    options: Option<&Bound<'_, PyDict>>,
 ) -> PyResult<()> {
+    let options = StringsOptions::new("dump_strings", file_path, bytes, options)?;
     py.detach(|| {
-        if matches!(file_path, Some(_)) && matches!(bytes, Some(_)) {
-            return Err(StringsException::new_err(
-                "You can't specify file_path and bytes",
-            ));
-        }
-        let encodings = encodings
-            .iter()
-            .map(|e| RustEncoding::from_str(e))
-            .collect::<Result<Vec<RustEncoding>, _>>()?;
-        let result: ErrorResult;
-        if let Some(file_path) = file_path {
-            let strings_config = RustFileConfig::new(&file_path)
-                .with_min_length(min_length)
-                .with_encodings(encodings)
-                .with_buffer_size(buffer_size);
-            result = r_dump_strings(&strings_config, output_file);
-        } else if let Some(bytes) = bytes {
-            let strings_config = RustBytesConfig::new(bytes)
-                .with_min_length(min_length)
-                .with_encodings(encodings);
-            result = r_dump_strings(&strings_config, output_file);
-        } else {
-            return Err(StringsException::new_err(
-                "You must specify file_path or bytes",
-            ));
-        }
-        if let Err(error_message) = result {
-            return Err(StringsException::new_err(format!("{}", error_message)));
-        }
-        Ok(())
+        options.run(
+            |config| r_dump_strings(config, output_file.clone()),
+            |config| r_dump_strings(config, output_file.clone()),
+        )
     })
 }
 
@@ -151,11 +371,25 @@ fn dump_strings(
 #[pyo3(name = "rust_strings")]
 fn rust_strings(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(strings, m)?)?;
+    m.add_function(wrap_pyfunction!(extract_strings, m)?)?;
     m.add_function(wrap_pyfunction!(dump_strings, m)?)?;
+    m.add_class::<ExtractedString>()?;
     m.add("StringsException", m.py().get_type::<StringsException>())?;
     m.add(
         "EncodingNotFoundException",
         m.py().get_type::<EncodingNotFoundException>(),
     )?;
+    m.add(
+        "UnicodeScriptNotFoundException",
+        m.py().get_type::<UnicodeScriptNotFoundException>(),
+    )?;
+    m.add(
+        "BinaryFormatNotFoundException",
+        m.py().get_type::<BinaryFormatNotFoundException>(),
+    )?;
+    m.add(
+        "MaxLengthPolicyNotFoundException",
+        m.py().get_type::<MaxLengthPolicyNotFoundException>(),
+    )?;
     Ok(())
 }
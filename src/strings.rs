@@ -1,29 +1,131 @@
-use std::cell::RefCell;
+use std::cell::{RefCell, RefMut};
 use std::error::Error;
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
 use std::iter::Iterator;
 use std::path::{Path, PathBuf};
-use std::rc::Rc;
 use std::result::Result;
+use std::thread;
 
+use memmap2::Mmap;
+use object::ReadCache;
+
+use crate::archives::{
+    find_members, read_member, Member, DEFAULT_MAX_ARCHIVE_DEPTH, DEFAULT_MAX_MEMBER_SIZE,
+};
+use crate::binary_formats::{find_regions, BinaryFormat, Region};
+use crate::character_set::CharacterSet;
+use crate::decompression::{
+    decompress, detect_compression, read_compression, write_temp_file, Compression, Decompression,
+    NamedTempFile,
+};
 use crate::encodings::Encoding;
+use crate::extracted_string::ExtractedString;
+use crate::files::{find_files, FileError, FileFilter, Files};
+use crate::max_length::MaxLengthPolicy;
+use crate::parallel::extract_into_parallel;
+#[cfg(target_os = "linux")]
+use crate::process::{find_mappings, ProcessMemoryReader};
 use crate::strings_extractor::{new_strings_extractor, StringsExtractor};
 use crate::strings_writer::{JsonWriter, StringWriter, VectorWriter};
+use crate::unicode_scripts::UnicodeScript;
 use crate::ErrorResult;
 
 const DEFAULT_MIN_LENGTH: usize = 3;
 const DEFAULT_ENCODINGS: [Encoding; 1] = [Encoding::ASCII];
+const DEFAULT_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Members of an archive along with the config scanning their contents.
+type Members = (Vec<Member>, BytesConfig);
+
+/// Decompressed stream of an input written to a temporary file, along with the config scanning
+/// the file.
+type Decompressed = (NamedTempFile, FileConfig<'static>);
 
 pub trait Config {
     #[doc(hidden)]
-    fn consume<F>(&self, func: F) -> ErrorResult
+    fn get_reader(&self) -> Result<Box<dyn BufRead + '_>, Box<dyn Error>> {
+        self.get_range_reader(self.get_start_offset(), self.get_range_length())
+    }
+    /// Reader over `length` bytes from `start_offset`, or up to the end of the input
+    #[doc(hidden)]
+    fn get_range_reader(
+        &self,
+        start_offset: u64,
+        length: Option<u64>,
+    ) -> Result<Box<dyn BufRead + '_>, Box<dyn Error>>;
+    /// Regions scanned each on its own, `None` to scan the whole range at once
+    #[doc(hidden)]
+    fn get_regions(&self) -> Result<Option<Vec<Region>>, Box<dyn Error>> {
+        Ok(None)
+    }
+    /// Compression of the input, whose decompressed stream is then read by
+    /// [`Config::get_range_reader`]
+    #[doc(hidden)]
+    fn get_compression(&self) -> Result<Option<Compression>, Box<dyn Error>> {
+        Ok(None)
+    }
+    /// Files scanned each on their own, along with the config scanning them whose path is
+    /// replaced by the one of every file. `None` for a single input
+    #[doc(hidden)]
+    fn get_files(&self) -> Result<Option<(Files, FileConfig<'static>)>, Box<dyn Error>> {
+        Ok(None)
+    }
+    /// Members of the input scanned each on its own when the input is an archive, along with the
+    /// config scanning their contents. `None` to scan the input itself
+    #[doc(hidden)]
+    fn get_members(&self) -> Result<Option<Members>, Box<dyn Error>> {
+        Ok(None)
+    }
+    /// Decompressed stream of a compressed input that is read from several offsets, e.g. for its
+    /// sections or members, so that it is decompressed once. `None` to read the input itself
+    #[doc(hidden)]
+    fn get_decompressed(&self) -> Result<Option<Decompressed>, Box<dyn Error>> {
+        Ok(None)
+    }
+    #[doc(hidden)]
+    fn consume<F>(&self, mut func: F) -> ErrorResult
     where
-        F: FnMut(usize, u8) -> ErrorResult;
+        F: FnMut(u64, &[u8]) -> ErrorResult,
+    {
+        let mut reader = self.get_reader()?;
+        let mut offset = self.get_start_offset();
+        loop {
+            let buffer = reader.fill_buf()?;
+            if buffer.is_empty() {
+                return Ok(());
+            }
+            func(offset, buffer)?;
+            let length = buffer.len();
+            offset += length as u64;
+            reader.consume(length);
+        }
+    }
     #[doc(hidden)]
     fn get_min_length(&self) -> usize;
+    /// Maximum length of a string and what happens to longer ones, `None` for no maximum
+    #[doc(hidden)]
+    fn get_max_length(&self) -> Option<(usize, MaxLengthPolicy)>;
+    /// Whether only the strings ended by a null terminator are extracted
+    #[doc(hidden)]
+    fn get_null_terminated_only(&self) -> bool;
     #[doc(hidden)]
     fn get_encodings(&self) -> Vec<Encoding>;
+    #[doc(hidden)]
+    fn get_character_set(&self) -> CharacterSet;
+    #[doc(hidden)]
+    fn get_unicode_scripts(&self) -> Vec<UnicodeScript>;
+    #[doc(hidden)]
+    fn get_threads(&self) -> usize;
+    /// Offset of the first byte returned by the reader
+    #[doc(hidden)]
+    fn get_start_offset(&self) -> u64;
+    #[doc(hidden)]
+    fn get_range_length(&self) -> Option<u64>;
+    #[doc(hidden)]
+    fn get_buffer_size(&self) -> usize {
+        DEFAULT_BUFFER_SIZE
+    }
 }
 
 macro_rules! impl_config {
@@ -31,12 +133,35 @@ macro_rules! impl_config {
         fn get_min_length(&self) -> usize {
             self.min_length
         }
+        fn get_max_length(&self) -> Option<(usize, MaxLengthPolicy)> {
+            self.max_length
+                .map(|max_length| (max_length, self.max_length_policy))
+        }
+        fn get_null_terminated_only(&self) -> bool {
+            self.null_terminated_only
+        }
         fn get_encodings(&self) -> Vec<Encoding> {
             if self.encodings.is_empty() {
                 return DEFAULT_ENCODINGS.to_vec();
             }
             self.encodings.clone()
         }
+        fn get_character_set(&self) -> CharacterSet {
+            self.character_set
+        }
+        fn get_unicode_scripts(&self) -> Vec<UnicodeScript> {
+            self.unicode_scripts.clone()
+        }
+        fn get_threads(&self) -> usize {
+            self.threads
+        }
+        fn get_start_offset(&self) -> u64 {
+            self.start_offset
+        }
+        fn get_range_length(&self) -> Option<u64> {
+            self.end_offset
+                .map(|end_offset| end_offset.saturating_sub(self.start_offset))
+        }
     };
 }
 
@@ -47,6 +172,26 @@ macro_rules! impl_default {
             self
         }
 
+        /// Longest string in characters, longer strings are handled according to `policy`. A
+        /// maximum below the minimum length is raised to it.
+        pub fn with_max_length(mut self, max_length: usize, policy: MaxLengthPolicy) -> Self {
+            self.max_length = Some(max_length);
+            self.max_length_policy = policy;
+            self
+        }
+
+        /// Extract only the strings ended by a null character of their encoding, e.g. `0x00` in
+        /// ascii or `0x0000` in utf-16, which are likely C strings rather than random printable
+        /// bytes. Strings ended by another character or by the end of the input are skipped, so
+        /// every string reports `null_terminated` as `Some(true)`. Strings aren't held in memory
+        /// until they end, so the chunks of a string split by [`MaxLengthPolicy::Split`] are
+        /// extracted as they are split and report `Some(false)`, only the last one is skipped
+        /// without the terminator.
+        pub fn with_null_terminated_only(mut self, null_terminated_only: bool) -> Self {
+            self.null_terminated_only = null_terminated_only;
+            self
+        }
+
         pub fn with_encoding(mut self, encoding: Encoding) -> Self {
             self.encodings.push(encoding);
             self
@@ -56,25 +201,223 @@ macro_rules! impl_default {
             self.encodings = encodings;
             self
         }
+
+        /// Ascii characters accepted in strings, defaults to printable ascii, tab, LF and CR.
+        pub fn with_character_set(mut self, character_set: CharacterSet) -> Self {
+            self.character_set = character_set;
+            self
+        }
+
+        /// Unicode scripts accepted by wide-character encodings on top of printable ASCII.
+        pub fn with_unicode_scripts(mut self, unicode_scripts: Vec<UnicodeScript>) -> Self {
+            self.unicode_scripts = unicode_scripts;
+            self
+        }
+
+        /// Scan the input with several threads, one buffer per thread at a time, 0 uses every
+        /// core. The strings are the same as with a single thread, in the same order.
+        pub fn with_threads(mut self, threads: usize) -> Self {
+            self.threads = threads;
+            self
+        }
+
+        /// Scan only `length` bytes from `start_offset`, offsets stay relative to the whole input.
+        pub fn with_range(mut self, start_offset: u64, length: u64) -> Self {
+            self.start_offset = start_offset;
+            self.end_offset = Some(start_offset.saturating_add(length));
+            self
+        }
+
+        /// Scan from `start_offset`, offsets stay relative to the whole input.
+        pub fn with_start_offset(mut self, start_offset: u64) -> Self {
+            self.start_offset = start_offset;
+            self
+        }
+
+        /// Stop scanning at `end_offset`, which isn't scanned.
+        pub fn with_end_offset(mut self, end_offset: u64) -> Self {
+            self.end_offset = Some(end_offset);
+            self
+        }
     };
 }
 
+/// Builders of the configs whose input can be parsed as an executable.
+macro_rules! impl_binary_format {
+    () => {
+        /// Parse the input as an executable and scan the contents of its sections only, strings
+        /// are attributed to their section and virtual address.
+        pub fn with_binary_format(mut self, binary_format: BinaryFormat) -> Self {
+            self.binary_format = binary_format;
+            self
+        }
+
+        /// Sections to scan when parsed with a binary format, e.g. `.rodata`, defaults to every
+        /// section with contents.
+        pub fn with_sections(mut self, sections: Vec<String>) -> Self {
+            self.sections = sections;
+            self
+        }
+    };
+}
+
+/// Builders of the configs whose input can be an archive.
+macro_rules! impl_archives {
+    () => {
+        /// Scan every file of a zip or tar archive as an input of its own instead of the archive,
+        /// strings carry the path of their member and offsets are relative to the member.
+        /// Archives in archives are scanned too, up to `with_max_archive_depth`. A member that
+        /// can't be read is handed to [`StringWriter::write_error`] like the files of a
+        /// [`DirectoryConfig`]. Deflated zip members need the `gzip` feature.
+        pub fn with_archives(mut self, archives: bool) -> Self {
+            self.archives = archives;
+            self
+        }
+
+        /// Levels of nested archives to descend into, defaults to 4.
+        pub fn with_max_archive_depth(mut self, max_archive_depth: usize) -> Self {
+            self.max_archive_depth = max_archive_depth;
+            self
+        }
+
+        /// Decompressed size above which a member isn't scanned, defaults to 256 MiB. Members are
+        /// decompressed into memory one at a time.
+        pub fn with_max_member_size(mut self, max_member_size: u64) -> Self {
+            self.max_member_size = max_member_size;
+            self
+        }
+    };
+}
+
+/// Builders of the configs whose input can be compressed.
+macro_rules! impl_decompression {
+    () => {
+        /// Scan the decompressed stream of a gzip, xz, zstd or bzip2 input instead of the input,
+        /// see [`Decompression::Auto`]. Offsets, including the range to scan and the sections of
+        /// binary formats, are in the decompressed stream and strings carry the compression.
+        ///
+        /// An input with a binary format or scanned as an archive is decompressed once to a
+        /// temporary file, which is then read from the offsets of its sections or members.
+        pub fn with_decompression(mut self, decompression: Decompression) -> Self {
+            self.decompression = decompression;
+            self
+        }
+    };
+}
+
+/// Archive methods of the configs whose input can be an archive.
+macro_rules! impl_archive_config {
+    () => {
+        fn get_members(&self) -> Result<Option<Members>, Box<dyn Error>> {
+            if !self.archives || self.max_archive_depth == 0 {
+                return Ok(None);
+            }
+            let members = match find_members(self, self.max_member_size)? {
+                Some(members) => members,
+                None => return Ok(None),
+            };
+            let member_config = BytesConfig {
+                bytes: vec![],
+                min_length: self.min_length,
+                max_length: self.max_length,
+                max_length_policy: self.max_length_policy,
+                null_terminated_only: self.null_terminated_only,
+                encodings: self.encodings.clone(),
+                character_set: self.character_set,
+                unicode_scripts: self.unicode_scripts.clone(),
+                threads: self.threads,
+                start_offset: 0,
+                end_offset: None,
+                binary_format: self.binary_format,
+                sections: self.sections.clone(),
+                archives: self.archives,
+                max_archive_depth: self.max_archive_depth - 1,
+                max_member_size: self.max_member_size,
+                decompression: self.decompression,
+            };
+            Ok(Some((members, member_config)))
+        }
+
+        fn get_decompressed(&self) -> Result<Option<Decompressed>, Box<dyn Error>> {
+            let archives = self.archives && self.max_archive_depth > 0;
+            if self.binary_format == BinaryFormat::Raw && !archives {
+                return Ok(None);
+            }
+            if self.get_compression()?.is_none() {
+                return Ok(None);
+            }
+            let file = write_temp_file(self.get_range_reader(0, None)?, self.get_buffer_size())?;
+            let file_config = FileConfig {
+                file_path: Path::new(""),
+                min_length: self.min_length,
+                max_length: self.max_length,
+                max_length_policy: self.max_length_policy,
+                null_terminated_only: self.null_terminated_only,
+                encodings: self.encodings.clone(),
+                character_set: self.character_set,
+                unicode_scripts: self.unicode_scripts.clone(),
+                threads: self.threads,
+                start_offset: self.start_offset,
+                end_offset: self.end_offset,
+                buffer_size: self.get_buffer_size(),
+                mmap: false,
+                binary_format: self.binary_format,
+                sections: self.sections.clone(),
+                archives: self.archives,
+                max_archive_depth: self.max_archive_depth,
+                max_member_size: self.max_member_size,
+                decompression: Decompression::None,
+            };
+            Ok(Some((file, file_config)))
+        }
+    };
+}
+
+#[derive(Clone)]
 pub struct FileConfig<'a> {
     pub file_path: &'a Path,
     pub min_length: usize,
+    pub max_length: Option<usize>,
+    pub max_length_policy: MaxLengthPolicy,
+    pub null_terminated_only: bool,
     pub encodings: Vec<Encoding>,
+    pub character_set: CharacterSet,
+    pub unicode_scripts: Vec<UnicodeScript>,
+    pub threads: usize,
+    pub start_offset: u64,
+    pub end_offset: Option<u64>,
     pub buffer_size: usize,
+    pub mmap: bool,
+    pub binary_format: BinaryFormat,
+    pub sections: Vec<String>,
+    pub archives: bool,
+    pub max_archive_depth: usize,
+    pub max_member_size: u64,
+    pub decompression: Decompression,
 }
 
 impl<'a> FileConfig<'a> {
-    const DEFAULT_BUFFER_SIZE: usize = 1024 * 1024;
-
     pub fn new(file_path: &'a Path) -> Self {
         FileConfig {
             file_path,
             min_length: DEFAULT_MIN_LENGTH,
+            max_length: None,
+            max_length_policy: MaxLengthPolicy::Truncate,
+            null_terminated_only: false,
             encodings: vec![],
-            buffer_size: FileConfig::DEFAULT_BUFFER_SIZE,
+            character_set: CharacterSet::default(),
+            unicode_scripts: vec![],
+            threads: 1,
+            start_offset: 0,
+            end_offset: None,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+            mmap: false,
+            binary_format: BinaryFormat::Raw,
+            sections: vec![],
+            archives: false,
+            max_archive_depth: DEFAULT_MAX_ARCHIVE_DEPTH,
+            max_member_size: DEFAULT_MAX_MEMBER_SIZE,
+            decompression: Decompression::None,
         }
     }
 
@@ -83,25 +426,275 @@ impl<'a> FileConfig<'a> {
         self
     }
 
+    /// Map the file into memory instead of copying it into buffers. The mapped file is scanned in
+    /// place, which only helps when scanning a big file `with_threads`: with a single thread it is
+    /// no faster than a buffer, and can be slower. Pipes, special files and files that can't be
+    /// mapped are read with a buffer anyway.
+    ///
+    /// The file must not be modified while strings are extracted.
+    pub fn with_mmap(mut self, mmap: bool) -> Self {
+        self.mmap = mmap;
+        self
+    }
+
     impl_default!();
+    impl_binary_format!();
+    impl_archives!();
+    impl_decompression!();
+}
+
+impl<'a> FileConfig<'a> {
+    /// Reader over a range of the file, or of its decompressed stream, which owns the file unlike
+    /// the reader of [`Config::get_range_reader`].
+    fn open_range_reader(
+        &self,
+        start_offset: u64,
+        length: Option<u64>,
+    ) -> Result<Box<dyn BufRead>, Box<dyn Error>> {
+        let compression = match self.get_compression()? {
+            Some(compression) => compression,
+            None => return self.open_file_range_reader(start_offset, length),
+        };
+        let reader = self.open_file_range_reader(0, None)?;
+        read_range(
+            decompress(compression, reader, self.buffer_size)?,
+            start_offset,
+            length,
+        )
+    }
+
+    /// Reader over a range of the bytes of the file.
+    fn open_file_range_reader(
+        &self,
+        start_offset: u64,
+        length: Option<u64>,
+    ) -> Result<Box<dyn BufRead>, Box<dyn Error>> {
+        let mut file = File::open(self.file_path)?;
+        let metadata = file.metadata()?;
+        if self.mmap && metadata.is_file() {
+            // Safety: the caller promises the file isn't modified, see `with_mmap`
+            if let Ok(mmap) = unsafe { Mmap::map(&file) } {
+                let position = usize::try_from(start_offset)
+                    .unwrap_or(usize::MAX)
+                    .min(mmap.len());
+                let end = length
+                    .and_then(|length| usize::try_from(length).ok())
+                    .map_or(mmap.len(), |length| {
+                        position.saturating_add(length).min(mmap.len())
+                    });
+                // Every thread scans a buffer of the same window, see `extract_into_parallel`
+                return Ok(Box::new(MmapReader {
+                    mmap,
+                    position,
+                    end,
+                    window_size: self.buffer_size.saturating_mul(thread_count(self.threads)),
+                }));
+            }
+        }
+        if metadata.is_file() {
+            file.seek(SeekFrom::Start(start_offset))?;
+            let reader = BufReader::with_capacity(self.buffer_size, file);
+            return read_range(reader, 0, length);
+        }
+        let reader = BufReader::with_capacity(self.buffer_size, file);
+        read_range(reader, start_offset, length)
+    }
 }
 
 impl<'a> Config for FileConfig<'a> {
-    fn consume<F>(&self, mut func: F) -> ErrorResult
-    where
-        F: FnMut(usize, u8) -> ErrorResult,
-    {
-        let file_result = File::open(self.file_path);
-        if let Err(err) = file_result {
-            return Err(Box::new(err));
-        }
-        let file = file_result.unwrap();
-        let buf_reader = BufReader::with_capacity(self.buffer_size, file);
-        buf_reader
-            .bytes()
-            .enumerate()
-            .try_for_each(|(i, b)| func(i, b.unwrap()))?;
-        Ok(())
+    fn get_buffer_size(&self) -> usize {
+        self.buffer_size
+    }
+
+    fn get_range_reader(
+        &self,
+        start_offset: u64,
+        length: Option<u64>,
+    ) -> Result<Box<dyn BufRead + '_>, Box<dyn Error>> {
+        self.open_range_reader(start_offset, length)
+    }
+
+    fn get_compression(&self) -> Result<Option<Compression>, Box<dyn Error>> {
+        // Pipes and special files can't be read twice, they are never decompressed
+        if self.decompression == Decompression::None || !self.file_path.metadata()?.is_file() {
+            return Ok(None);
+        }
+        read_compression(File::open(self.file_path)?)
+    }
+
+    fn get_regions(&self) -> Result<Option<Vec<Region>>, Box<dyn Error>> {
+        if self.binary_format == BinaryFormat::Raw {
+            return Ok(None);
+        }
+        // A compressed input is decompressed to a file first, see `Config::get_decompressed`
+        let file = ReadCache::new(File::open(self.file_path)?);
+        let regions = find_regions(self.binary_format, &file, &self.sections)?;
+        Ok(Some(regions))
+    }
+
+    impl_archive_config!();
+    impl_config!();
+}
+
+/// Config over several files and directories, every file is scanned on its own and its strings
+/// carry its name.
+///
+/// A file that can't be scanned doesn't stop the extraction, it is handed to
+/// [`StringWriter::write_error`] and returned as a [`FileError`] by [`strings_iter`]. Functions
+/// collecting the strings, such as [`extract_strings`], skip it.
+///
+/// Examples:
+/// ```
+/// use rust_strings::{DirectoryConfig, extract_strings};
+/// use std::path::PathBuf;
+///
+/// let config = DirectoryConfig::new(vec![PathBuf::from("/usr/lib")])
+///     .with_recursive(true)
+///     .with_include(vec![String::from("*.so")])
+///     .with_exclude(vec![String::from("**/python*")]);
+/// let extracted_strings = extract_strings(&config);
+/// ```
+pub struct DirectoryConfig {
+    pub paths: Vec<PathBuf>,
+    pub recursive: bool,
+    pub follow_symlinks: bool,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub min_length: usize,
+    pub max_length: Option<usize>,
+    pub max_length_policy: MaxLengthPolicy,
+    pub null_terminated_only: bool,
+    pub encodings: Vec<Encoding>,
+    pub character_set: CharacterSet,
+    pub unicode_scripts: Vec<UnicodeScript>,
+    pub threads: usize,
+    pub start_offset: u64,
+    pub end_offset: Option<u64>,
+    pub buffer_size: usize,
+    pub mmap: bool,
+    pub binary_format: BinaryFormat,
+    pub sections: Vec<String>,
+    pub archives: bool,
+    pub max_archive_depth: usize,
+    pub max_member_size: u64,
+    pub decompression: Decompression,
+}
+
+impl DirectoryConfig {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        DirectoryConfig {
+            paths,
+            recursive: false,
+            follow_symlinks: false,
+            include: vec![],
+            exclude: vec![],
+            min_length: DEFAULT_MIN_LENGTH,
+            max_length: None,
+            max_length_policy: MaxLengthPolicy::Truncate,
+            null_terminated_only: false,
+            encodings: vec![],
+            character_set: CharacterSet::default(),
+            unicode_scripts: vec![],
+            threads: 1,
+            start_offset: 0,
+            end_offset: None,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+            mmap: false,
+            binary_format: BinaryFormat::Raw,
+            sections: vec![],
+            archives: false,
+            max_archive_depth: DEFAULT_MAX_ARCHIVE_DEPTH,
+            max_member_size: DEFAULT_MAX_MEMBER_SIZE,
+            decompression: Decompression::None,
+        }
+    }
+
+    /// Scan the files of subdirectories too, by default only the files directly in a directory
+    /// are scanned.
+    pub fn with_recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Follow symlinks found in directories, which are skipped by default. Paths given to
+    /// [`DirectoryConfig::new`] are always followed.
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+
+    /// Glob patterns of the files to scan in directories, e.g. `*.dll`, defaults to every file.
+    /// Patterns match the whole path, and `*` matches `/` too.
+    pub fn with_include(mut self, include: Vec<String>) -> Self {
+        self.include = include;
+        self
+    }
+
+    /// Glob patterns of the files and directories to skip, e.g. `**/.git`.
+    pub fn with_exclude(mut self, exclude: Vec<String>) -> Self {
+        self.exclude = exclude;
+        self
+    }
+
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+
+    /// Map every file into memory, see [`FileConfig::with_mmap`].
+    pub fn with_mmap(mut self, mmap: bool) -> Self {
+        self.mmap = mmap;
+        self
+    }
+
+    impl_default!();
+    impl_binary_format!();
+    impl_archives!();
+    impl_decompression!();
+}
+
+impl Config for DirectoryConfig {
+    fn get_buffer_size(&self) -> usize {
+        self.buffer_size
+    }
+
+    fn get_range_reader(
+        &self,
+        _start_offset: u64,
+        _length: Option<u64>,
+    ) -> Result<Box<dyn BufRead + '_>, Box<dyn Error>> {
+        Err("Every file of a directory config is read on its own".into())
+    }
+
+    fn get_files(&self) -> Result<Option<(Files, FileConfig<'static>)>, Box<dyn Error>> {
+        let filter = FileFilter {
+            recursive: self.recursive,
+            follow_symlinks: self.follow_symlinks,
+            include: &self.include,
+            exclude: &self.exclude,
+        };
+        let file_config = FileConfig {
+            file_path: Path::new(""),
+            min_length: self.min_length,
+            max_length: self.max_length,
+            max_length_policy: self.max_length_policy,
+            null_terminated_only: self.null_terminated_only,
+            encodings: self.encodings.clone(),
+            character_set: self.character_set,
+            unicode_scripts: self.unicode_scripts.clone(),
+            threads: self.threads,
+            start_offset: self.start_offset,
+            end_offset: self.end_offset,
+            buffer_size: self.buffer_size,
+            mmap: self.mmap,
+            binary_format: self.binary_format,
+            sections: self.sections.clone(),
+            archives: self.archives,
+            max_archive_depth: self.max_archive_depth,
+            max_member_size: self.max_member_size,
+            decompression: self.decompression,
+        };
+        Ok(Some((find_files(&self.paths, &filter)?, file_config)))
     }
 
     impl_config!();
@@ -109,7 +702,15 @@ impl<'a> Config for FileConfig<'a> {
 
 pub struct StdinConfig {
     pub min_length: usize,
+    pub max_length: Option<usize>,
+    pub max_length_policy: MaxLengthPolicy,
+    pub null_terminated_only: bool,
     pub encodings: Vec<Encoding>,
+    pub character_set: CharacterSet,
+    pub unicode_scripts: Vec<UnicodeScript>,
+    pub threads: usize,
+    pub start_offset: u64,
+    pub end_offset: Option<u64>,
     pub buffer_size: usize,
 }
 
@@ -120,13 +721,19 @@ impl Default for StdinConfig {
 }
 
 impl StdinConfig {
-    const DEFAULT_BUFFER_SIZE: usize = 1024 * 1024;
-
     pub fn new() -> Self {
         StdinConfig {
             min_length: DEFAULT_MIN_LENGTH,
+            max_length: None,
+            max_length_policy: MaxLengthPolicy::Truncate,
+            null_terminated_only: false,
             encodings: vec![],
-            buffer_size: StdinConfig::DEFAULT_BUFFER_SIZE,
+            character_set: CharacterSet::default(),
+            unicode_scripts: vec![],
+            threads: 1,
+            start_offset: 0,
+            end_offset: None,
+            buffer_size: DEFAULT_BUFFER_SIZE,
         }
     }
 
@@ -139,25 +746,249 @@ impl StdinConfig {
 }
 
 impl Config for StdinConfig {
-    fn consume<F>(&self, mut func: F) -> ErrorResult
-    where
-        F: FnMut(usize, u8) -> ErrorResult,
-    {
-        let buf_reader = BufReader::with_capacity(self.buffer_size, std::io::stdin());
-        buf_reader
-            .bytes()
-            .enumerate()
-            .try_for_each(|(i, b)| func(i, b.unwrap()))?;
-        Ok(())
+    fn get_buffer_size(&self) -> usize {
+        self.buffer_size
+    }
+
+    fn get_range_reader(
+        &self,
+        start_offset: u64,
+        length: Option<u64>,
+    ) -> Result<Box<dyn BufRead + '_>, Box<dyn Error>> {
+        let reader = BufReader::with_capacity(self.buffer_size, std::io::stdin());
+        read_range(reader, start_offset, length)
     }
 
     impl_config!();
 }
 
+/// Config over the memory of a running process on Linux, every readable mapping of
+/// `/proc/<pid>/maps` is scanned on its own. Offsets are the virtual addresses in the process,
+/// and strings carry the name of their mapping.
+///
+/// Reading the memory of another process needs the permission to trace it, see `ptrace(2)`. The
+/// process keeps running while it is scanned, pages that can't be read are skipped and end the
+/// strings before them with `null_terminated` as `None`.
+///
+/// Examples:
+/// ```
+/// use rust_strings::{ProcessConfig, extract_strings};
+///
+/// let config = ProcessConfig::new(std::process::id()).with_min_length(20);
+/// let extracted_strings = extract_strings(&config);
+/// ```
+#[cfg(target_os = "linux")]
+pub struct ProcessConfig {
+    pub pid: u32,
+    pub min_length: usize,
+    pub max_length: Option<usize>,
+    pub max_length_policy: MaxLengthPolicy,
+    pub null_terminated_only: bool,
+    pub encodings: Vec<Encoding>,
+    pub character_set: CharacterSet,
+    pub unicode_scripts: Vec<UnicodeScript>,
+    pub threads: usize,
+    pub start_offset: u64,
+    pub end_offset: Option<u64>,
+    pub buffer_size: usize,
+}
+
+#[cfg(target_os = "linux")]
+impl ProcessConfig {
+    pub fn new(pid: u32) -> Self {
+        ProcessConfig {
+            pid,
+            min_length: DEFAULT_MIN_LENGTH,
+            max_length: None,
+            max_length_policy: MaxLengthPolicy::Truncate,
+            null_terminated_only: false,
+            encodings: vec![],
+            character_set: CharacterSet::default(),
+            unicode_scripts: vec![],
+            threads: 1,
+            start_offset: 0,
+            end_offset: None,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+        }
+    }
+
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+
+    impl_default!();
+}
+
+#[cfg(target_os = "linux")]
+impl Config for ProcessConfig {
+    fn get_buffer_size(&self) -> usize {
+        self.buffer_size
+    }
+
+    fn get_range_reader(
+        &self,
+        start_offset: u64,
+        length: Option<u64>,
+    ) -> Result<Box<dyn BufRead + '_>, Box<dyn Error>> {
+        let reader = ProcessMemoryReader::new(self.pid, start_offset, length)?;
+        Ok(Box::new(BufReader::with_capacity(self.buffer_size, reader)))
+    }
+
+    fn get_regions(&self) -> Result<Option<Vec<Region>>, Box<dyn Error>> {
+        Ok(Some(find_mappings(self.pid)?))
+    }
+
+    impl_config!();
+}
+
+/// Config over any reader, such as a socket, a decompressor or a `Cursor`.
+///
+/// The reader is consumed as strings are extracted, so a second extraction continues from where
+/// the first one stopped.
+///
+/// Examples:
+/// ```
+/// use rust_strings::{ReaderConfig, strings};
+/// use std::io::Cursor;
+///
+/// let config = ReaderConfig::new(Cursor::new(b"test\x00")).with_buffer_size(1024);
+/// assert_eq!(vec![(String::from("test"), 0)], strings(&config).unwrap());
+/// ```
+pub struct ReaderConfig<R> {
+    reader: RefCell<R>,
+    pub min_length: usize,
+    pub max_length: Option<usize>,
+    pub max_length_policy: MaxLengthPolicy,
+    pub null_terminated_only: bool,
+    pub encodings: Vec<Encoding>,
+    pub character_set: CharacterSet,
+    pub unicode_scripts: Vec<UnicodeScript>,
+    pub threads: usize,
+    pub start_offset: u64,
+    pub end_offset: Option<u64>,
+    pub buffer_size: usize,
+}
+
+impl<R: Read> ReaderConfig<R> {
+    pub fn new(reader: R) -> Self {
+        ReaderConfig {
+            reader: RefCell::new(reader),
+            min_length: DEFAULT_MIN_LENGTH,
+            max_length: None,
+            max_length_policy: MaxLengthPolicy::Truncate,
+            null_terminated_only: false,
+            encodings: vec![],
+            character_set: CharacterSet::default(),
+            unicode_scripts: vec![],
+            threads: 1,
+            start_offset: 0,
+            end_offset: None,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+        }
+    }
+
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader.into_inner()
+    }
+
+    impl_default!();
+}
+
+/// Skips the bytes before the range, which can't be seeked, and stops at the end of the range.
+fn read_range<'a, R: BufRead + 'a>(
+    mut reader: R,
+    skip: u64,
+    length: Option<u64>,
+) -> Result<Box<dyn BufRead + 'a>, Box<dyn Error>> {
+    std::io::copy(&mut Read::take(&mut reader, skip), &mut std::io::sink())?;
+    Ok(match length {
+        Some(length) => Box::new(reader.take(length)),
+        None => Box::new(reader),
+    })
+}
+
+/// Hands out slices of a mapped file, `window_size` bytes at a time.
+struct MmapReader {
+    mmap: Mmap,
+    position: usize,
+    end: usize,
+    window_size: usize,
+}
+
+impl Read for MmapReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let length = self.fill_buf()?.read(buf)?;
+        self.consume(length);
+        Ok(length)
+    }
+}
+
+impl BufRead for MmapReader {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        let end = self
+            .position
+            .saturating_add(self.window_size.max(1))
+            .min(self.end);
+        Ok(&self.mmap[self.position..end])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.position = (self.position + amt).min(self.end);
+    }
+}
+
+/// Reads through the borrowed reader of a [`ReaderConfig`].
+struct BorrowedReader<'a, R>(RefMut<'a, R>);
+
+impl<'a, R: Read> Read for BorrowedReader<'a, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl<R: Read> Config for ReaderConfig<R> {
+    fn get_buffer_size(&self) -> usize {
+        self.buffer_size
+    }
+
+    fn get_range_reader(
+        &self,
+        start_offset: u64,
+        length: Option<u64>,
+    ) -> Result<Box<dyn BufRead + '_>, Box<dyn Error>> {
+        let reader = self.reader.try_borrow_mut()?;
+        let reader = BufReader::with_capacity(self.buffer_size, BorrowedReader(reader));
+        read_range(reader, start_offset, length)
+    }
+
+    impl_config!();
+}
+
+#[derive(Clone)]
 pub struct BytesConfig {
     pub bytes: Vec<u8>,
     pub min_length: usize,
+    pub max_length: Option<usize>,
+    pub max_length_policy: MaxLengthPolicy,
+    pub null_terminated_only: bool,
     pub encodings: Vec<Encoding>,
+    pub character_set: CharacterSet,
+    pub unicode_scripts: Vec<UnicodeScript>,
+    pub threads: usize,
+    pub start_offset: u64,
+    pub end_offset: Option<u64>,
+    pub binary_format: BinaryFormat,
+    pub sections: Vec<String>,
+    pub archives: bool,
+    pub max_archive_depth: usize,
+    pub max_member_size: u64,
+    pub decompression: Decompression,
 }
 
 impl BytesConfig {
@@ -165,58 +996,541 @@ impl BytesConfig {
         BytesConfig {
             bytes,
             min_length: DEFAULT_MIN_LENGTH,
+            max_length: None,
+            max_length_policy: MaxLengthPolicy::Truncate,
+            null_terminated_only: false,
             encodings: vec![],
+            character_set: CharacterSet::default(),
+            unicode_scripts: vec![],
+            threads: 1,
+            start_offset: 0,
+            end_offset: None,
+            binary_format: BinaryFormat::Raw,
+            sections: vec![],
+            archives: false,
+            max_archive_depth: DEFAULT_MAX_ARCHIVE_DEPTH,
+            max_member_size: DEFAULT_MAX_MEMBER_SIZE,
+            decompression: Decompression::None,
         }
     }
 
     impl_default!();
+    impl_binary_format!();
+    impl_archives!();
+    impl_decompression!();
 }
 
 impl Config for BytesConfig {
-    fn consume<F>(&self, mut func: F) -> ErrorResult
-    where
-        F: FnMut(usize, u8) -> ErrorResult,
-    {
-        self.bytes
-            .iter()
-            .enumerate()
-            .try_for_each(|(i, b)| func(i, *b))?;
-        Ok(())
+    fn get_range_reader(
+        &self,
+        start_offset: u64,
+        length: Option<u64>,
+    ) -> Result<Box<dyn BufRead + '_>, Box<dyn Error>> {
+        if let Some(compression) = self.get_compression()? {
+            let reader = Box::new(self.bytes.as_slice());
+            let reader = decompress(compression, reader, DEFAULT_BUFFER_SIZE)?;
+            return read_range(reader, start_offset, length);
+        }
+        let bytes_length = self.bytes.len() as u64;
+        let start = start_offset.min(bytes_length);
+        let end = length.map_or(bytes_length, |length| {
+            start.saturating_add(length).min(bytes_length)
+        });
+        Ok(Box::new(&self.bytes[start as usize..end as usize]))
     }
 
+    fn get_compression(&self) -> Result<Option<Compression>, Box<dyn Error>> {
+        if self.decompression == Decompression::None {
+            return Ok(None);
+        }
+        Ok(detect_compression(&self.bytes))
+    }
+
+    fn get_regions(&self) -> Result<Option<Vec<Region>>, Box<dyn Error>> {
+        if self.binary_format == BinaryFormat::Raw {
+            return Ok(None);
+        }
+        // A compressed input is decompressed to a file first, see `Config::get_decompressed`
+        let regions = find_regions(self.binary_format, self.bytes.as_slice(), &self.sections)?;
+        Ok(Some(regions))
+    }
+
+    impl_archive_config!();
     impl_config!();
 }
 
-fn _strings<T: Config, W: StringWriter>(
+pub(crate) fn new_strings_extractors<T: Config>(
     strings_config: &T,
-    strings_writer: Rc<RefCell<W>>,
-) -> ErrorResult {
+) -> Vec<Box<dyn StringsExtractor>> {
     let min_length = strings_config.get_min_length();
-    let mut strings_extractors: Vec<Box<dyn StringsExtractor>> = strings_config
+    let max_length = strings_config.get_max_length();
+    let null_terminated_only = strings_config.get_null_terminated_only();
+    let character_set = strings_config.get_character_set();
+    let unicode_scripts = strings_config.get_unicode_scripts();
+    strings_config
         .get_encodings()
         .iter()
-        .map(|e| new_strings_extractor(strings_writer.clone(), *e, min_length))
-        .collect();
-    strings_config.consume(|offset: usize, c: u8| {
-        strings_extractors
-            .iter_mut()
-            .try_for_each(|strings_extractor| -> ErrorResult {
-                if strings_extractor.can_consume(c) {
-                    strings_extractor.consume(offset as u64, c)?;
-                } else {
-                    strings_extractor.stop_consume()?;
-                }
-                Ok(())
-            })?;
-        Ok(())
-    })?;
+        .map(|e| {
+            new_strings_extractor(
+                *e,
+                min_length,
+                max_length,
+                null_terminated_only,
+                character_set,
+                unicode_scripts.clone(),
+            )
+        })
+        .collect()
+}
+
+/// Number of threads scanning the input, 0 being every core.
+fn thread_count(threads: usize) -> usize {
+    match threads {
+        0 => thread::available_parallelism().map_or(1, |threads| threads.get()),
+        threads => threads,
+    }
+}
+
+fn consume_buffer(
+    strings_extractors: &mut [Box<dyn StringsExtractor>],
+    offset: u64,
+    buffer: &[u8],
+) {
     strings_extractors
         .iter_mut()
-        .try_for_each(|strings_extractor| -> ErrorResult {
-            strings_extractor.stop_consume()?;
-            Ok(())
-        })?;
-    Ok(())
+        .for_each(|strings_extractor| strings_extractor.consume(offset, buffer))
+}
+
+fn stop_consume(strings_extractors: &mut [Box<dyn StringsExtractor>]) {
+    strings_extractors
+        .iter_mut()
+        .for_each(|strings_extractor| strings_extractor.stop_consume())
+}
+
+/// Takes the strings ended so far in the order they ended, strings ended by the same byte are
+/// ordered like the encodings.
+fn take_strings(strings_extractors: &mut [Box<dyn StringsExtractor>]) -> Vec<ExtractedString> {
+    if let [strings_extractor] = strings_extractors {
+        return strings_extractor
+            .take_strings()
+            .into_iter()
+            .map(|(_, extracted_string)| extracted_string)
+            .collect();
+    }
+    let mut extracted_strings: Vec<(u64, usize, ExtractedString)> = strings_extractors
+        .iter_mut()
+        .enumerate()
+        .flat_map(|(i, strings_extractor)| {
+            strings_extractor
+                .take_strings()
+                .into_iter()
+                .map(move |(position, extracted_string)| (position, i, extracted_string))
+        })
+        .collect();
+    extracted_strings.sort_by_key(|(position, i, _)| (*position, *i));
+    extracted_strings
+        .into_iter()
+        .map(|(_, _, extracted_string)| extracted_string)
+        .collect()
+}
+
+/// Regions of the config within its range, `None` when the whole range is scanned at once.
+fn get_regions_in_range<T: Config>(
+    strings_config: &T,
+) -> Result<Option<Vec<Region>>, Box<dyn Error>> {
+    let start_offset = strings_config.get_start_offset();
+    let end_offset = strings_config
+        .get_range_length()
+        .map_or(u64::MAX, |length| start_offset.saturating_add(length));
+    let regions = match strings_config.get_regions()? {
+        Some(regions) => regions,
+        None => return Ok(None),
+    };
+    let mut regions_in_range = vec![];
+    for region in regions
+        .iter()
+        .filter_map(|region| region.clip(start_offset, end_offset))
+    {
+        if region.skip_if_zero && is_zero_filled(strings_config, &region)? {
+            continue;
+        }
+        regions_in_range.push(region);
+    }
+    Ok(Some(regions_in_range))
+}
+
+/// Whether every byte of the region is zero, read up to the first one that isn't.
+fn is_zero_filled<T: Config>(strings_config: &T, region: &Region) -> Result<bool, Box<dyn Error>> {
+    let mut reader = strings_config.get_range_reader(region.offset, Some(region.length))?;
+    loop {
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            return Ok(true);
+        }
+        if buffer.iter().any(|byte| *byte != 0) {
+            return Ok(false);
+        }
+        let length = buffer.len();
+        reader.consume(length);
+    }
+}
+
+/// Opens the reader over `length` bytes from an offset of the input, or up to its end.
+type OpenRange<'a> = dyn Fn(u64, Option<u64>) -> Result<Box<dyn BufRead + 'a>, Box<dyn Error>> + 'a;
+
+/// Scans a member of an archive at once with the config scanning its contents.
+type ScanMember<'a> =
+    dyn Fn(Member, &BytesConfig) -> Vec<Result<ExtractedString, Box<dyn Error>>> + 'a;
+
+/// Opens the strings of a file of a multi-file input with the config scanning its files.
+type OpenFile<'a> = dyn Fn(&FileConfig<'static>, &Path) -> StringsIter<'a> + 'a;
+
+/// How the input of a config is scanned.
+enum Input {
+    /// `length` bytes from an offset of the input, or up to its end
+    Range(u64, Option<u64>),
+    Regions(Vec<Region>),
+    /// Files of a multi-file input and the config scanning them
+    Files(Files, FileConfig<'static>),
+    /// Members of an archive and the config scanning their contents
+    Members(Vec<Member>, BytesConfig),
+    /// File holding the decompressed stream of a compressed input, the config scanning it and
+    /// how it is scanned
+    Decompressed(NamedTempFile, FileConfig<'static>, Box<Input>),
+}
+
+/// How the input of a config is scanned along with its compression, by both [`extract_into`]
+/// and [`strings_iter`].
+fn open_input<T: Config>(input_config: &T) -> Result<(Input, Option<Compression>), Box<dyn Error>> {
+    if let Some((files, file_config)) = input_config.get_files()? {
+        return Ok((Input::Files(files, file_config), None));
+    }
+    let compression = input_config.get_compression()?;
+    if let Some((file, file_config)) = input_config.get_decompressed()? {
+        let input = match open_input(&get_file_config(&file_config, file.path()))?.0 {
+            // Only the file isn't decompressed again, its members are like the ones of the input
+            Input::Members(members, member_config) => Input::Members(
+                members,
+                BytesConfig {
+                    decompression: Decompression::Auto,
+                    ..member_config
+                },
+            ),
+            input => input,
+        };
+        let input = Input::Decompressed(file, file_config, Box::new(input));
+        return Ok((input, compression));
+    }
+    if let Some((members, member_config)) = input_config.get_members()? {
+        return Ok((Input::Members(members, member_config), compression));
+    }
+    let input = match get_regions_in_range(input_config)? {
+        Some(regions) => Input::Regions(regions),
+        None => Input::Range(
+            input_config.get_start_offset(),
+            input_config.get_range_length(),
+        ),
+    };
+    Ok((input, compression))
+}
+
+/// Config scanning a file of a multi-file input, from the config scanning its files.
+fn get_file_config<'a>(file_config: &FileConfig<'static>, file_path: &'a Path) -> FileConfig<'a> {
+    FileConfig {
+        file_path,
+        ..file_config.clone()
+    }
+}
+
+/// Lazy iterator over extracted strings, created by [`strings_iter`].
+pub struct StringsIter<'a> {
+    reader: Option<Box<dyn BufRead + 'a>>,
+    reader_error: Option<Box<dyn Error>>,
+    strings_extractors: Vec<Box<dyn StringsExtractor>>,
+    extracted_strings: std::vec::IntoIter<ExtractedString>,
+    offset: u64,
+    min_length: usize,
+    null_terminated_only: bool,
+    parsed_string: Option<ExtractedString>,
+    region: Option<Region>,
+    regions: std::vec::IntoIter<Region>,
+    open_range: Box<OpenRange<'a>>,
+    new_strings_extractors: Box<dyn Fn() -> Vec<Box<dyn StringsExtractor>> + 'a>,
+    /// Files left to scan of a multi-file input and the config scanning them, `None` for a
+    /// single input
+    files: Option<(
+        std::vec::IntoIter<Result<PathBuf, FileError>>,
+        FileConfig<'static>,
+    )>,
+    /// Path and strings of the file being scanned
+    file_strings: Option<(String, Box<StringsIter<'a>>)>,
+    open_file: Box<OpenFile<'a>>,
+    /// Members left to scan of an archive and the config scanning their contents, members are
+    /// small enough to be scanned at once
+    members: Option<(std::vec::IntoIter<Member>, BytesConfig)>,
+    member_strings: std::vec::IntoIter<Result<ExtractedString, Box<dyn Error>>>,
+    scan_member: Box<ScanMember<'a>>,
+    /// Temporary file holding the decompressed stream of the input and its strings
+    decompressed: Option<(NamedTempFile, Box<StringsIter<'a>>)>,
+    /// Compression of the input, strings are decompressed from it
+    compression: Option<Compression>,
+}
+
+impl<'a> StringsIter<'a> {
+    fn new<T: Config>(strings_config: &'a T) -> Self {
+        let open_range = Box::new(move |start_offset, length| {
+            strings_config.get_range_reader(start_offset, length)
+        });
+        Self::with_input(
+            strings_config,
+            open_input(strings_config),
+            open_range,
+            Box::new(move |member, member_config| {
+                collect_member_strings(strings_config, member, member_config)
+            }),
+        )
+    }
+
+    /// Iterator over a file of a multi-file input.
+    fn with_file<T: Config>(
+        strings_config: &'a T,
+        file_config: &FileConfig<'static>,
+        file_path: &Path,
+    ) -> Self {
+        let input = open_input(&get_file_config(file_config, file_path));
+        Self::with_file_input(strings_config, file_config, file_path, input)
+    }
+
+    /// Iterator over a file whose input is already opened.
+    fn with_file_input<T: Config>(
+        strings_config: &'a T,
+        file_config: &FileConfig<'static>,
+        file_path: &Path,
+        input: Result<(Input, Option<Compression>), Box<dyn Error>>,
+    ) -> Self {
+        let range_file_config = file_config.clone();
+        let range_file_path = file_path.to_path_buf();
+        let open_range: Box<OpenRange<'a>> = Box::new(move |start_offset, length| {
+            get_file_config(&range_file_config, &range_file_path)
+                .open_range_reader(start_offset, length)
+        });
+        let archive_file_config = file_config.clone();
+        let archive_file_path = file_path.to_path_buf();
+        let scan_member = Box::new(move |member, member_config: &BytesConfig| {
+            let file_config = get_file_config(&archive_file_config, &archive_file_path);
+            collect_member_strings(&file_config, member, member_config)
+        });
+        Self::with_input(strings_config, input, open_range, scan_member)
+    }
+
+    fn with_input<T: Config>(
+        strings_config: &'a T,
+        input: Result<(Input, Option<Compression>), Box<dyn Error>>,
+        open_range: Box<OpenRange<'a>>,
+        scan_member: Box<ScanMember<'a>>,
+    ) -> Self {
+        let mut strings_iter = StringsIter {
+            reader: None,
+            reader_error: None,
+            strings_extractors: new_strings_extractors(strings_config),
+            extracted_strings: Vec::new().into_iter(),
+            offset: 0,
+            min_length: strings_config.get_min_length(),
+            null_terminated_only: strings_config.get_null_terminated_only(),
+            parsed_string: None,
+            region: None,
+            regions: Vec::new().into_iter(),
+            open_range,
+            new_strings_extractors: Box::new(move || new_strings_extractors(strings_config)),
+            files: None,
+            file_strings: None,
+            open_file: Box::new(move |file_config, file_path| {
+                Self::with_file(strings_config, file_config, file_path)
+            }),
+            members: None,
+            member_strings: Vec::new().into_iter(),
+            scan_member,
+            decompressed: None,
+            compression: None,
+        };
+        // An error opening the input is reported by the first call to `next`
+        let input = match input {
+            Ok((input, compression)) => {
+                strings_iter.compression = compression;
+                input
+            }
+            Err(err) => {
+                strings_iter.reader_error = Some(err);
+                return strings_iter;
+            }
+        };
+        match input {
+            Input::Range(start_offset, length) => {
+                strings_iter.offset = start_offset;
+                match (strings_iter.open_range)(start_offset, length) {
+                    Ok(reader) => strings_iter.reader = Some(reader),
+                    Err(err) => strings_iter.reader_error = Some(err),
+                }
+            }
+            Input::Regions(regions) => strings_iter.regions = regions.into_iter(),
+            Input::Files(files, file_config) => {
+                strings_iter.files = Some((files.into_iter(), file_config))
+            }
+            Input::Members(members, member_config) => {
+                strings_iter.members = Some((members.into_iter(), member_config))
+            }
+            Input::Decompressed(file, file_config, input) => {
+                let file_strings = Self::with_file_input(
+                    strings_config,
+                    &file_config,
+                    file.path(),
+                    Ok((*input, None)),
+                );
+                strings_iter.decompressed = Some((file, Box::new(file_strings)));
+            }
+        }
+        strings_iter
+    }
+
+    /// Starts scanning the next region with new extractors, returns false after the last one.
+    fn open_next_region(&mut self) -> Result<bool, Box<dyn Error>> {
+        let region = match self.regions.next() {
+            Some(region) => region,
+            None => return Ok(false),
+        };
+        if region.parsed_string.is_some() {
+            self.parsed_string =
+                region.get_parsed_string(self.min_length, self.null_terminated_only);
+            return Ok(true);
+        }
+        self.reader = Some((self.open_range)(region.offset, Some(region.length))?);
+        self.strings_extractors = (self.new_strings_extractors)();
+        self.offset = region.offset;
+        self.region = Some(region);
+        Ok(true)
+    }
+
+    /// Feeds the next buffer of the input to the extractors, returns false at the end of input.
+    fn consume_next_buffer(&mut self) -> Result<bool, Box<dyn Error>> {
+        let reader = match self.reader.as_mut() {
+            Some(reader) => reader,
+            None => return self.open_next_region(),
+        };
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            self.reader = None;
+            stop_consume(&mut self.strings_extractors);
+            return Ok(true);
+        }
+        consume_buffer(&mut self.strings_extractors, self.offset, buffer);
+        let length = buffer.len();
+        self.offset += length as u64;
+        reader.consume(length);
+        Ok(true)
+    }
+
+    /// Next string of the files of a multi-file input, a file that can't be scanned is returned
+    /// as a [`FileError`] and the next files are scanned anyway.
+    fn next_file_string(&mut self) -> Option<Result<ExtractedString, Box<dyn Error>>> {
+        loop {
+            if let Some((file_name, file_strings)) = self.file_strings.as_mut() {
+                match file_strings.next() {
+                    Some(Ok(mut extracted_string)) => {
+                        extracted_string.file_name = Some(file_name.clone());
+                        return Some(Ok(extracted_string));
+                    }
+                    // A member of an archive that can't be read doesn't stop the file
+                    Some(Err(err)) => match err.downcast::<FileError>() {
+                        Ok(file_error) => {
+                            return Some(Err(Box::new(file_error.in_parent(file_name))));
+                        }
+                        Err(err) => {
+                            let file_error = FileError::new(file_name.clone(), err);
+                            self.file_strings = None;
+                            return Some(Err(Box::new(file_error)));
+                        }
+                    },
+                    None => self.file_strings = None,
+                }
+            }
+            let (files, file_config) = self.files.as_mut()?;
+            match files.next()? {
+                Ok(file_path) => {
+                    let file_strings = (self.open_file)(file_config, &file_path);
+                    let file_name = file_path.to_string_lossy().into_owned();
+                    self.file_strings = Some((file_name, Box::new(file_strings)));
+                }
+                Err(file_error) => return Some(Err(Box::new(file_error))),
+            }
+        }
+    }
+
+    /// Next string of the members of an archive, a member that can't be read is returned as a
+    /// [`FileError`] and the next members are scanned anyway.
+    fn next_member_string(&mut self) -> Option<Result<ExtractedString, Box<dyn Error>>> {
+        loop {
+            if let Some(result) = self.member_strings.next() {
+                return Some(result.map(|mut extracted_string| {
+                    extracted_string.compression =
+                        extracted_string.compression.or(self.compression);
+                    extracted_string
+                }));
+            }
+            let (members, member_config) = self.members.as_mut()?;
+            let member = members.next()?;
+            self.member_strings = (self.scan_member)(member, member_config).into_iter();
+        }
+    }
+}
+
+impl<'a> Iterator for StringsIter<'a> {
+    type Item = Result<ExtractedString, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(err) = self.reader_error.take() {
+            return Some(Err(err));
+        }
+        if self.files.is_some() {
+            return self.next_file_string();
+        }
+        if self.members.is_some() {
+            return self.next_member_string();
+        }
+        if let Some((_, file_strings)) = self.decompressed.as_mut() {
+            let compression = self.compression;
+            return file_strings.next().map(|result| {
+                result.map(|mut extracted_string| {
+                    extracted_string.compression = extracted_string.compression.or(compression);
+                    extracted_string
+                })
+            });
+        }
+        loop {
+            if let Some(extracted_string) = self.extracted_strings.next() {
+                return Some(Ok(extracted_string));
+            }
+            match self.consume_next_buffer() {
+                Ok(true) => {}
+                Ok(false) => return None,
+                Err(err) => {
+                    self.reader = None;
+                    self.regions = Vec::new().into_iter();
+                    return Some(Err(err));
+                }
+            }
+            let mut extracted_strings = take_strings(&mut self.strings_extractors);
+            if let Some(region) = &self.region {
+                extracted_strings
+                    .iter_mut()
+                    .for_each(|extracted_string| region.annotate(extracted_string));
+            }
+            extracted_strings.extend(self.parsed_string.take());
+            extracted_strings.iter_mut().for_each(|extracted_string| {
+                extracted_string.compression = extracted_string.compression.or(self.compression)
+            });
+            self.extracted_strings = extracted_strings.into_iter();
+        }
+    }
 }
 
 /// Extract strings from binary data.
@@ -247,10 +1561,370 @@ fn _strings<T: Config, W: StringWriter>(
 /// assert_eq!(vec![(String::from("test"), 0)], extracted_strings.unwrap());
 /// ```
 pub fn strings<T: Config>(strings_config: &T) -> Result<Vec<(String, u64)>, Box<dyn Error>> {
-    let vector_writer = Rc::new(RefCell::new(VectorWriter::new()));
-    _strings(strings_config, vector_writer.clone())?;
-    let result = Ok(vector_writer.borrow_mut().get_strings());
-    result
+    let extracted_strings = extract_strings(strings_config)?;
+    Ok(extracted_strings
+        .into_iter()
+        .map(|extracted_string| (extracted_string.text, extracted_string.offset))
+        .collect())
+}
+
+/// Extract strings from binary data, along with their location and encoding.
+///
+/// Examples:
+/// ```
+/// use rust_strings::{BytesConfig, extract_strings, Encoding};
+///
+/// let config = BytesConfig::new(b"\x01test\x00".to_vec());
+/// let extracted_strings = extract_strings(&config).unwrap();
+/// assert_eq!("test", extracted_strings[0].text);
+/// assert_eq!(1, extracted_strings[0].offset);
+/// assert_eq!(5, extracted_strings[0].end_offset);
+/// assert_eq!(Encoding::ASCII, extracted_strings[0].encoding);
+/// assert_eq!(Some(true), extracted_strings[0].null_terminated);
+/// ```
+pub fn extract_strings<T: Config>(
+    strings_config: &T,
+) -> Result<Vec<ExtractedString>, Box<dyn Error>> {
+    let mut vector_writer = VectorWriter::new();
+    extract_into(strings_config, &mut vector_writer)?;
+    Ok(vector_writer.get_strings())
+}
+
+/// Extract strings from binary data into a custom [`StringWriter`].
+///
+/// Strings are handed to the writer as soon as they end, without being collected first. An error
+/// returned by the writer stops the extraction and is returned as is.
+///
+/// Examples:
+/// ```
+/// use rust_strings::{BytesConfig, extract_into, ExtractedString, StringWriter};
+/// use std::error::Error;
+///
+/// struct CountWriter {
+///     count: usize,
+/// }
+///
+/// impl StringWriter for CountWriter {
+///     fn write_string(&mut self, _: ExtractedString) -> Result<(), Box<dyn Error>> {
+///         self.count += 1;
+///         Ok(())
+///     }
+/// }
+///
+/// let config = BytesConfig::new(b"first\x00second\x00".to_vec());
+/// let mut writer = CountWriter { count: 0 };
+/// extract_into(&config, &mut writer).unwrap();
+/// assert_eq!(2, writer.count);
+/// ```
+pub fn extract_into<T: Config, W: StringWriter>(strings_config: &T, writer: &mut W) -> ErrorResult {
+    extract_input_into(strings_config, writer)
+}
+
+/// Extracts the strings of an input, file by file if it has several, member by member if it is
+/// an archive and region by region if it has regions.
+fn extract_input_into<T: Config, W: StringWriter>(
+    strings_config: &T,
+    writer: &mut W,
+) -> ErrorResult {
+    let (input, compression) = open_input(strings_config)?;
+    let writer = &mut CompressionWriter {
+        writer,
+        compression,
+    };
+    extract_opened_input_into(strings_config, input, writer)
+}
+
+/// Extracts the strings of an input opened by [`open_input`].
+fn extract_opened_input_into<T: Config>(
+    strings_config: &T,
+    input: Input,
+    writer: &mut CompressionWriter<'_>,
+) -> ErrorResult {
+    let regions = match input {
+        Input::Range(..) => return extract_range_into(strings_config, writer),
+        Input::Regions(regions) => regions,
+        Input::Files(files, file_config) => return extract_files_into(files, &file_config, writer),
+        Input::Members(members, member_config) => {
+            return extract_members_into(strings_config, members, &member_config, writer)
+        }
+        Input::Decompressed(file, file_config, input) => {
+            let file_config = get_file_config(&file_config, file.path());
+            return extract_opened_input_into(&file_config, *input, writer);
+        }
+    };
+    let min_length = strings_config.get_min_length();
+    let null_terminated_only = strings_config.get_null_terminated_only();
+    regions.iter().try_for_each(|region| {
+        if region.parsed_string.is_some() {
+            return match region.get_parsed_string(min_length, null_terminated_only) {
+                Some(extracted_string) => writer.write_string(extracted_string),
+                None => Ok(()),
+            };
+        }
+        let region_config = RegionConfig {
+            strings_config,
+            region,
+        };
+        let mut region_writer = RegionWriter {
+            writer: &mut *writer,
+            region,
+        };
+        extract_range_into(&region_config, &mut region_writer)
+    })
+}
+
+/// Extracts the strings of every file on its own, a file that can't be scanned is handed to the
+/// writer and the next files are scanned anyway.
+fn extract_files_into(
+    files: Files,
+    file_config: &FileConfig<'static>,
+    writer: &mut dyn StringWriter,
+) -> ErrorResult {
+    files.into_iter().try_for_each(|file| {
+        let file_path = match file {
+            Ok(file_path) => file_path,
+            Err(file_error) => return writer.write_error(file_error),
+        };
+        let mut file_writer = FileWriter {
+            writer: &mut *writer,
+            file_name: file_path.to_string_lossy().into_owned(),
+            is_writer_error: false,
+        };
+        match extract_input_into(&get_file_config(file_config, &file_path), &mut file_writer) {
+            Ok(()) => Ok(()),
+            Err(err) if file_writer.is_writer_error => Err(err),
+            Err(err) => {
+                let file_name = file_path.to_string_lossy().into_owned();
+                writer.write_error(FileError::new(file_name, err))
+            }
+        }
+    })
+}
+
+/// Extracts the strings of every member of an archive on its own, a member that can't be read
+/// is handed to the writer and the next members are scanned anyway.
+fn extract_members_into<T: Config>(
+    strings_config: &T,
+    members: Vec<Member>,
+    member_config: &BytesConfig,
+    writer: &mut dyn StringWriter,
+) -> ErrorResult {
+    members.into_iter().try_for_each(|member| {
+        let member_config = match read_member(strings_config, &member) {
+            Ok(bytes) => BytesConfig {
+                bytes,
+                ..member_config.clone()
+            },
+            Err(err) => return writer.write_error(FileError::new(member.path().to_owned(), err)),
+        };
+        let mut member_writer = MemberWriter {
+            writer: &mut *writer,
+            member_path: member.path(),
+            is_writer_error: false,
+        };
+        match extract_input_into(&member_config, &mut member_writer) {
+            Ok(()) => Ok(()),
+            Err(err) if member_writer.is_writer_error => Err(err),
+            Err(err) => writer.write_error(FileError::new(member.path().to_owned(), err)),
+        }
+    })
+}
+
+/// Strings of a member of an archive, and the members of nested archives that can't be read.
+fn collect_member_strings<T: Config>(
+    strings_config: &T,
+    member: Member,
+    member_config: &BytesConfig,
+) -> Vec<Result<ExtractedString, Box<dyn Error>>> {
+    let mut results_writer = ResultsWriter::default();
+    if let Err(err) = extract_members_into(
+        strings_config,
+        vec![member],
+        member_config,
+        &mut results_writer,
+    ) {
+        results_writer.results.push(Err(err));
+    }
+    results_writer.results
+}
+
+fn extract_range_into<T: Config, W: StringWriter>(
+    strings_config: &T,
+    writer: &mut W,
+) -> ErrorResult {
+    let threads = thread_count(strings_config.get_threads());
+    if threads > 1 {
+        return extract_into_parallel(strings_config, writer, threads);
+    }
+    let mut strings_extractors = new_strings_extractors(strings_config);
+    strings_config.consume(|offset: u64, buffer: &[u8]| {
+        consume_buffer(&mut strings_extractors, offset, buffer);
+        take_strings(&mut strings_extractors)
+            .into_iter()
+            .try_for_each(|extracted_string| writer.write_string(extracted_string))
+    })?;
+    stop_consume(&mut strings_extractors);
+    take_strings(&mut strings_extractors)
+        .into_iter()
+        .try_for_each(|extracted_string| writer.write_string(extracted_string))
+}
+
+/// A region of the input of a config, scanned as if it were the whole input.
+struct RegionConfig<'a, T> {
+    strings_config: &'a T,
+    region: &'a Region,
+}
+
+impl<T: Config> Config for RegionConfig<'_, T> {
+    fn get_range_reader(
+        &self,
+        start_offset: u64,
+        length: Option<u64>,
+    ) -> Result<Box<dyn BufRead + '_>, Box<dyn Error>> {
+        self.strings_config.get_range_reader(start_offset, length)
+    }
+
+    fn get_min_length(&self) -> usize {
+        self.strings_config.get_min_length()
+    }
+
+    fn get_max_length(&self) -> Option<(usize, MaxLengthPolicy)> {
+        self.strings_config.get_max_length()
+    }
+
+    fn get_null_terminated_only(&self) -> bool {
+        self.strings_config.get_null_terminated_only()
+    }
+
+    fn get_encodings(&self) -> Vec<Encoding> {
+        self.strings_config.get_encodings()
+    }
+
+    fn get_character_set(&self) -> CharacterSet {
+        self.strings_config.get_character_set()
+    }
+
+    fn get_unicode_scripts(&self) -> Vec<UnicodeScript> {
+        self.strings_config.get_unicode_scripts()
+    }
+
+    fn get_threads(&self) -> usize {
+        self.strings_config.get_threads()
+    }
+
+    fn get_start_offset(&self) -> u64 {
+        self.region.offset
+    }
+
+    fn get_range_length(&self) -> Option<u64> {
+        Some(self.region.length)
+    }
+
+    fn get_buffer_size(&self) -> usize {
+        self.strings_config.get_buffer_size()
+    }
+}
+
+/// Attributes the strings of a region to it.
+struct RegionWriter<'a, W> {
+    writer: &'a mut W,
+    region: &'a Region,
+}
+
+impl<W: StringWriter> StringWriter for RegionWriter<'_, W> {
+    fn write_string(&mut self, mut extracted_string: ExtractedString) -> ErrorResult {
+        self.region.annotate(&mut extracted_string);
+        self.writer.write_string(extracted_string)
+    }
+}
+
+/// Attributes the strings of a compressed input to its compression, unless they were
+/// decompressed from a compressed member of it.
+struct CompressionWriter<'a> {
+    writer: &'a mut dyn StringWriter,
+    compression: Option<Compression>,
+}
+
+impl StringWriter for CompressionWriter<'_> {
+    fn write_string(&mut self, mut extracted_string: ExtractedString) -> ErrorResult {
+        extracted_string.compression = extracted_string.compression.or(self.compression);
+        self.writer.write_string(extracted_string)
+    }
+
+    fn write_error(&mut self, file_error: FileError) -> ErrorResult {
+        self.writer.write_error(file_error)
+    }
+}
+
+/// Attributes the strings of a file of a multi-file input to it.
+struct FileWriter<'a> {
+    writer: &'a mut dyn StringWriter,
+    file_name: String,
+    /// Whether the extraction stopped on an error of the writer rather than of the file
+    is_writer_error: bool,
+}
+
+impl StringWriter for FileWriter<'_> {
+    fn write_string(&mut self, mut extracted_string: ExtractedString) -> ErrorResult {
+        extracted_string.file_name = Some(self.file_name.clone());
+        let result = self.writer.write_string(extracted_string);
+        self.is_writer_error = result.is_err();
+        result
+    }
+
+    fn write_error(&mut self, file_error: FileError) -> ErrorResult {
+        let result = self
+            .writer
+            .write_error(file_error.in_parent(&self.file_name));
+        self.is_writer_error = result.is_err();
+        result
+    }
+}
+
+/// Attributes the strings of a member of an archive to it, after the members nested in it.
+struct MemberWriter<'a> {
+    writer: &'a mut dyn StringWriter,
+    member_path: &'a str,
+    /// Whether the extraction stopped on an error of the writer rather than of the member
+    is_writer_error: bool,
+}
+
+impl StringWriter for MemberWriter<'_> {
+    fn write_string(&mut self, mut extracted_string: ExtractedString) -> ErrorResult {
+        extracted_string.member = Some(match extracted_string.member {
+            Some(member) => format!("{}/{}", self.member_path, member),
+            None => self.member_path.to_owned(),
+        });
+        let result = self.writer.write_string(extracted_string);
+        self.is_writer_error = result.is_err();
+        result
+    }
+
+    fn write_error(&mut self, file_error: FileError) -> ErrorResult {
+        let result = self
+            .writer
+            .write_error(file_error.in_parent(self.member_path));
+        self.is_writer_error = result.is_err();
+        result
+    }
+}
+
+/// Collects strings along with the errors of the files that can't be scanned.
+#[derive(Default)]
+struct ResultsWriter {
+    results: Vec<Result<ExtractedString, Box<dyn Error>>>,
+}
+
+impl StringWriter for ResultsWriter {
+    fn write_string(&mut self, extracted_string: ExtractedString) -> ErrorResult {
+        self.results.push(Ok(extracted_string));
+        Ok(())
+    }
+
+    fn write_error(&mut self, file_error: FileError) -> ErrorResult {
+        self.results.push(Err(Box::new(file_error)));
+        Ok(())
+    }
 }
 
 /// Dump strings from binary data to json file.
@@ -265,8 +1939,46 @@ pub fn strings<T: Config>(strings_config: &T) -> Result<Vec<(String, u64)>, Box<
 ///
 pub fn dump_strings<T: Config>(strings_config: &T, output: PathBuf) -> ErrorResult {
     let output_file = File::create(output)?;
-    let vector_writer = Rc::new(RefCell::new(JsonWriter::new(output_file)));
-    _strings(strings_config, vector_writer.clone())?;
-    vector_writer.borrow_mut().finish()?;
+    let mut json_writer = JsonWriter::new(output_file);
+    extract_into(strings_config, &mut json_writer)?;
+    json_writer.finish()?;
     Ok(())
 }
+
+/// Dump strings from binary data to json file, with all of the fields of [`ExtractedString`].
+///
+/// Examples:
+/// ```
+/// use std::path::PathBuf;
+/// use rust_strings::{BytesConfig, dump_extracted_strings};
+///
+/// let config = BytesConfig::new(b"test\x00".to_vec());
+/// dump_extracted_strings(&config, PathBuf::from("strings.json"));
+/// ```
+pub fn dump_extracted_strings<T: Config>(strings_config: &T, output: PathBuf) -> ErrorResult {
+    let output_file = File::create(output)?;
+    let mut json_writer = JsonWriter::new(output_file).with_details(true);
+    extract_into(strings_config, &mut json_writer)?;
+    json_writer.finish()?;
+    Ok(())
+}
+
+/// Lazily extract strings from binary data.
+///
+/// The input is read one buffer at a time as the iterator advances, so memory stays bounded
+/// regardless of the input size.
+///
+/// Examples:
+/// ```
+/// use rust_strings::{BytesConfig, strings_iter};
+///
+/// let config = BytesConfig::new(b"first\x00second\x00third".to_vec());
+/// let first_strings: Vec<String> = strings_iter(&config)
+///     .take(2)
+///     .map(|extracted_string| extracted_string.unwrap().text)
+///     .collect();
+/// assert_eq!(vec![String::from("first"), String::from("second")], first_strings);
+/// ```
+pub fn strings_iter<T: Config>(strings_config: &T) -> StringsIter<'_> {
+    StringsIter::new(strings_config)
+}
@@ -1,171 +1,784 @@
-use std::cell::RefCell;
+use std::any::Any;
 use std::mem::take;
-use std::rc::Rc;
 
+use crate::character_set::CharacterSet;
+use crate::code_pages;
 use crate::encodings::Encoding;
-use crate::strings_writer::StringWriter;
-use crate::ErrorResult;
+use crate::extracted_string::ExtractedString;
+use crate::max_length::{MaxLengthPolicy, TRUNCATION_MARKER};
+use crate::unicode_scripts::UnicodeScript;
 
-pub trait StringsExtractor {
-    fn can_consume(&self, c: u8) -> bool;
-    fn consume(&mut self, offset: u64, c: u8) -> ErrorResult;
-    fn stop_consume(&mut self) -> ErrorResult;
+pub trait StringsExtractor: Send {
+    /// Scans `bytes`, which start at `offset` in the input.
+    fn consume(&mut self, offset: u64, bytes: &[u8]);
+    /// Called at the end of the input
+    fn stop_consume(&mut self);
+    /// Takes the strings ended so far, each with the offset of the byte that ended it.
+    fn take_strings(&mut self) -> Vec<(u64, ExtractedString)>;
+    fn as_any(&self) -> &dyn Any;
+    /// Whether both extractors would extract the same strings from now on.
+    fn has_same_state(&self, other: &dyn StringsExtractor) -> bool;
 }
 
-pub struct AsciiExtractor<T> {
-    writer: Rc<RefCell<T>>,
+macro_rules! impl_same_state {
+    () => {
+        fn take_strings(&mut self) -> Vec<(u64, ExtractedString)> {
+            take(&mut self.current_string.strings)
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn has_same_state(&self, other: &dyn StringsExtractor) -> bool {
+            other
+                .as_any()
+                .downcast_ref::<Self>()
+                .is_some_and(|other| self == other)
+        }
+    };
+}
+
+/// Position of the strings ended by the end of the input.
+const END_OF_INPUT: u64 = u64::MAX;
+
+/// The string an extractor is in the middle of, and the strings it ended so far.
+struct CurrentString {
+    encoding: Encoding,
     min_length: usize,
-    current_string: Vec<u8>,
+    max_length: Option<usize>,
+    max_length_policy: MaxLengthPolicy,
+    /// Whether only the strings ended by a null terminator are emitted
+    null_terminated_only: bool,
+    /// Text of the first `max_length` characters
+    text: String,
+    /// Length in characters
+    length: usize,
+    /// Whether the string follows a chunk split at the maximum length
+    is_continuation: bool,
     offset: u64,
-    is_start_writing: bool,
+    end_offset: u64,
+    strings: Vec<(u64, ExtractedString)>,
+}
+
+/// Extractor of single-byte encodings, ascii and code pages, scanning runs of printable bytes.
+#[derive(PartialEq)]
+pub struct SingleByteExtractor {
+    /// Decoded character of every printable byte
+    table: [Option<char>; 256],
+    current_string: CurrentString,
+}
+
+/// Layout of the lead and trail bytes of a multi-byte charset
+#[derive(Copy, Clone, PartialEq)]
+enum MultiByteCharset {
+    ShiftJis,
+    Gb18030,
+    Big5,
+    EucKr,
+}
+
+enum SequenceStatus {
+    Complete,
+    Incomplete,
+    Invalid,
 }
 
-pub struct Utf16Extractor<T> {
-    writer: Rc<RefCell<T>>,
+pub struct MultiByteExtractor {
+    character_set: CharacterSet,
+    charset: MultiByteCharset,
+    current_string: CurrentString,
+    pending_bytes: Vec<u8>,
+    pending_offset: u64,
+}
+
+#[derive(PartialEq)]
+pub struct Utf16Extractor {
+    character_set: CharacterSet,
     is_big_endian: bool,
-    is_last_char_null: Option<bool>,
-    min_length: usize,
-    current_string: Vec<u8>,
-    offset: Option<u64>,
-    is_start_writing: bool,
+    unicode_scripts: Vec<UnicodeScript>,
+    current_string: CurrentString,
+    /// First byte of the code unit being read
+    pending_byte: Option<(u64, u8)>,
+    high_surrogate: Option<(u64, u16)>,
+}
+
+pub struct Utf32Extractor {
+    character_set: CharacterSet,
+    is_big_endian: bool,
+    unicode_scripts: Vec<UnicodeScript>,
+    current_string: CurrentString,
+    /// Bytes of the code unit being read
+    pending_bytes: Vec<u8>,
+    pending_offset: u64,
+}
+
+pub struct Utf8Extractor {
+    character_set: CharacterSet,
+    current_string: CurrentString,
+    pending_bytes: Vec<u8>,
+    pending_offset: u64,
 }
 
-pub fn new_strings_extractor<'a, T>(
-    writer: Rc<RefCell<T>>,
+pub fn new_strings_extractor(
     encoding: Encoding,
     min_length: usize,
-) -> Box<dyn StringsExtractor + 'a>
-where
-    T: StringWriter + 'a,
-{
+    max_length: Option<(usize, MaxLengthPolicy)>,
+    null_terminated_only: bool,
+    character_set: CharacterSet,
+    unicode_scripts: Vec<UnicodeScript>,
+) -> Box<dyn StringsExtractor> {
+    let current_string = CurrentString::new(encoding, min_length, max_length, null_terminated_only);
     match encoding {
-        Encoding::ASCII => Box::new(AsciiExtractor {
-            writer,
-            min_length,
-            current_string: Vec::with_capacity(min_length),
-            offset: 0,
-            is_start_writing: false,
+        Encoding::ASCII => {
+            let table = std::array::from_fn(|c| {
+                Some(c as u8)
+                    .filter(|c| character_set.contains(*c))
+                    .map(char::from)
+            });
+            Box::new(SingleByteExtractor {
+                table,
+                current_string,
+            })
+        }
+        Encoding::LATIN1 => {
+            new_code_page_extractor(current_string, &code_pages::LATIN1, character_set)
+        }
+        Encoding::CP1252 => {
+            new_code_page_extractor(current_string, &code_pages::CP1252, character_set)
+        }
+        Encoding::CP437 => {
+            new_code_page_extractor(current_string, &code_pages::CP437, character_set)
+        }
+        Encoding::KOI8R => {
+            new_code_page_extractor(current_string, &code_pages::KOI8R, character_set)
+        }
+        Encoding::CP037 => {
+            new_code_page_extractor(current_string, &code_pages::CP037, character_set)
+        }
+        Encoding::CP500 => {
+            new_code_page_extractor(current_string, &code_pages::CP500, character_set)
+        }
+        Encoding::CP1047 => {
+            new_code_page_extractor(current_string, &code_pages::CP1047, character_set)
+        }
+        Encoding::SHIFTJIS => {
+            new_multi_byte_extractor(current_string, MultiByteCharset::ShiftJis, character_set)
+        }
+        Encoding::GB18030 => {
+            new_multi_byte_extractor(current_string, MultiByteCharset::Gb18030, character_set)
+        }
+        Encoding::BIG5 => {
+            new_multi_byte_extractor(current_string, MultiByteCharset::Big5, character_set)
+        }
+        Encoding::EUCKR => {
+            new_multi_byte_extractor(current_string, MultiByteCharset::EucKr, character_set)
+        }
+        Encoding::UTF8 => Box::new(Utf8Extractor {
+            character_set,
+            current_string,
+            pending_bytes: Vec::with_capacity(4),
+            pending_offset: 0,
         }),
-        Encoding::UTF16LE => Box::new(Utf16Extractor {
-            writer,
-            is_big_endian: false,
-            is_last_char_null: None,
-            min_length,
-            current_string: Vec::with_capacity(min_length),
-            offset: None,
-            is_start_writing: false,
+        Encoding::UTF16LE | Encoding::UTF16BE => Box::new(Utf16Extractor {
+            character_set,
+            is_big_endian: matches!(encoding, Encoding::UTF16BE),
+            unicode_scripts,
+            current_string,
+            pending_byte: None,
+            high_surrogate: None,
         }),
-        Encoding::UTF16BE => Box::new(Utf16Extractor {
-            writer,
-            is_big_endian: true,
-            is_last_char_null: None,
-            min_length,
-            current_string: Vec::with_capacity(min_length),
-            offset: None,
-            is_start_writing: false,
+        Encoding::UTF32LE | Encoding::UTF32BE => Box::new(Utf32Extractor {
+            character_set,
+            is_big_endian: matches!(encoding, Encoding::UTF32BE),
+            unicode_scripts,
+            current_string,
+            pending_bytes: Vec::with_capacity(4),
+            pending_offset: 0,
         }),
     }
 }
 
-fn is_printable_character(c: u8) -> bool {
-    (32..=126).contains(&c) || (9..=10).contains(&c) || c == 13
+/// Printability is decided on the decoded character, so it works for non-ascii based code pages
+/// such as EBCDIC as well.
+fn new_code_page_extractor(
+    current_string: CurrentString,
+    code_page: &'static [char; 256],
+    character_set: CharacterSet,
+) -> Box<dyn StringsExtractor> {
+    let table = std::array::from_fn(|c| {
+        Some(code_page[c]).filter(|c| is_printable_decoded_character(*c, &character_set))
+    });
+    Box::new(SingleByteExtractor {
+        table,
+        current_string,
+    })
+}
+
+fn new_multi_byte_extractor(
+    current_string: CurrentString,
+    charset: MultiByteCharset,
+    character_set: CharacterSet,
+) -> Box<dyn StringsExtractor> {
+    Box::new(MultiByteExtractor {
+        character_set,
+        charset,
+        current_string,
+        pending_bytes: Vec::with_capacity(4),
+        pending_offset: 0,
+    })
+}
+
+/// Printability of a character decoded from a legacy charset, every mapped character but controls.
+fn is_printable_decoded_character(c: char, character_set: &CharacterSet) -> bool {
+    match c.is_ascii() {
+        true => character_set.contains(c as u8),
+        false => !c.is_control() && c != char::REPLACEMENT_CHARACTER,
+    }
+}
+
+fn is_printable_unicode_character(
+    c: char,
+    character_set: &CharacterSet,
+    unicode_scripts: &[UnicodeScript],
+) -> bool {
+    if c.is_ascii() {
+        return character_set.contains(c as u8);
+    }
+    unicode_scripts
+        .iter()
+        .any(|unicode_script| unicode_script.contains(c))
 }
 
-impl<T> StringsExtractor for AsciiExtractor<T>
-where
-    T: StringWriter,
-{
-    fn can_consume(&self, c: u8) -> bool {
-        is_printable_character(c)
+/// Returns the total length of the UTF-8 sequence started by `c`, or `None` if `c` can't start one.
+fn utf8_sequence_length(c: u8) -> Option<usize> {
+    match c {
+        0xC2..=0xDF => Some(2),
+        0xE0..=0xEF => Some(3),
+        0xF0..=0xF4 => Some(4),
+        _ => None,
     }
+}
 
-    fn consume(&mut self, offset: u64, c: u8) -> ErrorResult {
-        if self.is_start_writing {
-            self.writer.borrow_mut().write_char(c as char)?;
-        } else if self.current_string.len() == self.min_length - 1 && !self.is_start_writing {
-            // Fix case when min_length=1
-            if self.current_string.is_empty() {
-                self.offset = offset;
-            }
-            self.is_start_writing = true;
-            self.current_string.push(c);
-            self.writer
-                .borrow_mut()
-                .start_string_consume(take(&mut self.current_string), self.offset)?;
-        } else if self.current_string.is_empty() && !self.is_start_writing {
+fn is_utf8_continuation_byte(c: u8) -> bool {
+    (0x80..=0xBF).contains(&c)
+}
+
+fn is_high_surrogate(unit: u16) -> bool {
+    (0xD800..=0xDBFF).contains(&unit)
+}
+
+fn is_low_surrogate(unit: u16) -> bool {
+    (0xDC00..=0xDFFF).contains(&unit)
+}
+
+/// Offsets of an empty string are leftovers of the previous one, and ended strings aren't state.
+impl PartialEq for CurrentString {
+    fn eq(&self, other: &Self) -> bool {
+        self.encoding == other.encoding
+            && self.min_length == other.min_length
+            && self.max_length == other.max_length
+            && self.max_length_policy == other.max_length_policy
+            && self.null_terminated_only == other.null_terminated_only
+            && self.length == other.length
+            && self.text == other.text
+            && self.is_continuation == other.is_continuation
+            && (self.length == 0
+                || (self.offset == other.offset && self.end_offset == other.end_offset))
+    }
+}
+
+/// Pending bytes along with the offset of the first one, which is a leftover when there are none.
+fn is_same_pending(bytes: &[u8], offset: u64, other_bytes: &[u8], other_offset: u64) -> bool {
+    bytes == other_bytes && (bytes.is_empty() || offset == other_offset)
+}
+
+impl PartialEq for MultiByteExtractor {
+    fn eq(&self, other: &Self) -> bool {
+        self.charset == other.charset
+            && self.current_string == other.current_string
+            && is_same_pending(
+                &self.pending_bytes,
+                self.pending_offset,
+                &other.pending_bytes,
+                other.pending_offset,
+            )
+    }
+}
+
+impl PartialEq for Utf32Extractor {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_big_endian == other.is_big_endian
+            && self.current_string == other.current_string
+            && is_same_pending(
+                &self.pending_bytes,
+                self.pending_offset,
+                &other.pending_bytes,
+                other.pending_offset,
+            )
+    }
+}
+
+impl PartialEq for Utf8Extractor {
+    fn eq(&self, other: &Self) -> bool {
+        self.current_string == other.current_string
+            && is_same_pending(
+                &self.pending_bytes,
+                self.pending_offset,
+                &other.pending_bytes,
+                other.pending_offset,
+            )
+    }
+}
+
+impl CurrentString {
+    /// A maximum length below the minimum length is raised to it.
+    fn new(
+        encoding: Encoding,
+        min_length: usize,
+        max_length: Option<(usize, MaxLengthPolicy)>,
+        null_terminated_only: bool,
+    ) -> Self {
+        CurrentString {
+            encoding,
+            min_length,
+            max_length: max_length.map(|(max_length, _)| max_length.max(min_length).max(1)),
+            max_length_policy: max_length
+                .map_or_else(MaxLengthPolicy::default, |(_, policy)| policy),
+            null_terminated_only,
+            text: String::with_capacity(min_length),
+            length: 0,
+            is_continuation: false,
+            offset: 0,
+            end_offset: 0,
+            strings: vec![],
+        }
+    }
+
+    /// Number of characters that can be appended before reaching the maximum length.
+    fn room(&self) -> Option<usize> {
+        self.max_length
+            .map(|max_length| max_length.saturating_sub(self.length))
+    }
+
+    /// Appends a character whose bytes are at `offset..end_offset`.
+    fn push(&mut self, offset: u64, end_offset: u64, c: char) {
+        // Emitted at the last byte of the character like a string at its terminator, so that
+        // strings are in the same order whichever buffer the character starts in
+        if self.room() == Some(0) && self.max_length_policy == MaxLengthPolicy::Split {
+            self.emit(end_offset - 1, Some(false));
+            self.is_continuation = true;
+        }
+        if self.length == 0 {
             self.offset = offset;
-            self.current_string.push(c);
+        }
+        if self.room() != Some(0) {
+            self.text.push(c);
+        }
+        self.length += 1;
+        self.end_offset = end_offset;
+    }
+
+    /// Ends the string because of the byte at `position`, `null_terminated` is `None` when it
+    /// ends with the input.
+    fn finish(&mut self, position: u64, null_terminated: Option<bool>) {
+        // Chunks split before the end are already emitted, only the last one needs the terminator
+        if self.null_terminated_only && null_terminated != Some(true) {
+            self.text.clear();
+            self.length = 0;
         } else {
-            self.current_string.push(c);
+            self.emit(position, null_terminated);
         }
-        Ok(())
+        self.is_continuation = false;
     }
 
-    fn stop_consume(&mut self) -> ErrorResult {
-        if self.is_start_writing {
-            self.writer.borrow_mut().finish_string_consume()?;
+    /// Emits the string if it is long enough, and starts a new one.
+    fn emit(&mut self, position: u64, null_terminated: Option<bool>) {
+        let is_too_long = self
+            .max_length
+            .is_some_and(|max_length| self.length > max_length);
+        let is_dropped = is_too_long && self.max_length_policy == MaxLengthPolicy::Drop;
+        if self.length > 0
+            && (self.length >= self.min_length || self.is_continuation)
+            && !is_dropped
+        {
+            if is_too_long {
+                self.text.push_str(TRUNCATION_MARKER);
+            }
+            let extracted_string = ExtractedString {
+                text: take(&mut self.text),
+                offset: self.offset,
+                end_offset: self.end_offset,
+                byte_length: self.end_offset - self.offset,
+                encoding: self.encoding,
+                null_terminated,
+                section: None,
+                virtual_address: None,
+                relative_virtual_address: None,
+                resource: None,
+                architecture: None,
+                cfstring_address: None,
+                mapping: None,
+                file_name: None,
+                member: None,
+                compression: None,
+            };
+            self.strings.push((position, extracted_string));
         }
-        self.is_start_writing = false;
-        self.current_string.clear();
-        Ok(())
+        self.text.clear();
+        self.length = 0;
     }
 }
 
-impl<T> StringsExtractor for Utf16Extractor<T>
-where
-    T: StringWriter,
-{
-    fn can_consume(&self, c: u8) -> bool {
-        let is_char_null = c == 0;
-        match self.is_last_char_null {
-            None => {
-                (self.is_big_endian && is_char_null)
-                    || (!self.is_big_endian && is_printable_character(c))
+impl StringsExtractor for SingleByteExtractor {
+    fn consume(&mut self, offset: u64, bytes: &[u8]) {
+        let mut start = 0;
+        while start < bytes.len() {
+            let end = bytes[start..]
+                .iter()
+                .position(|c| self.table[*c as usize].is_none())
+                .map_or(bytes.len(), |length| start + length);
+            let current_string = &mut self.current_string;
+            if current_string.room().is_some_and(|room| room < end - start) {
+                for (index, c) in bytes[start..end].iter().enumerate() {
+                    let character_offset = offset + (start + index) as u64;
+                    if let Some(c) = self.table[*c as usize] {
+                        current_string.push(character_offset, character_offset + 1, c);
+                    }
+                }
+            } else if start < end {
+                if current_string.length == 0 {
+                    current_string.offset = offset + start as u64;
+                }
+                current_string.text.extend(
+                    bytes[start..end]
+                        .iter()
+                        .filter_map(|c| self.table[*c as usize]),
+                );
+                current_string.length += end - start;
+                current_string.end_offset = offset + end as u64;
+            }
+            if let Some(c) = bytes.get(end) {
+                self.current_string
+                    .finish(offset + end as u64, Some(*c == 0));
+            }
+            start = end + 1;
+        }
+    }
+
+    fn stop_consume(&mut self) {
+        self.current_string.finish(END_OF_INPUT, None);
+    }
+
+    impl_same_state!();
+}
+
+impl MultiByteCharset {
+    fn sequence_status(&self, bytes: &[u8]) -> SequenceStatus {
+        let is_trail_byte = |c: u8| match self {
+            MultiByteCharset::ShiftJis => matches!(c, 0x40..=0x7E | 0x80..=0xFC),
+            MultiByteCharset::Gb18030 => matches!(c, 0x40..=0x7E | 0x80..=0xFE),
+            MultiByteCharset::Big5 => matches!(c, 0x40..=0x7E | 0xA1..=0xFE),
+            MultiByteCharset::EucKr => matches!(c, 0xA1..=0xFE),
+        };
+        let is_lead_byte = match self {
+            MultiByteCharset::ShiftJis => matches!(bytes[0], 0x81..=0x9F | 0xE0..=0xFC),
+            MultiByteCharset::Gb18030 | MultiByteCharset::Big5 => {
+                matches!(bytes[0], 0x81..=0xFE)
             }
-            Some(is_last_char_null) => {
-                let is_char_printable = is_printable_character(c);
-                (!is_last_char_null && is_char_null) || (is_last_char_null && is_char_printable)
+            MultiByteCharset::EucKr => matches!(bytes[0], 0xA1..=0xFE),
+        };
+        let is_single_byte = match self {
+            // Half-width katakana
+            MultiByteCharset::ShiftJis => bytes[0] < 0x80 || matches!(bytes[0], 0xA1..=0xDF),
+            // The euro sign
+            MultiByteCharset::Gb18030 => bytes[0] <= 0x80,
+            MultiByteCharset::Big5 | MultiByteCharset::EucKr => bytes[0] < 0x80,
+        };
+        if is_single_byte {
+            return SequenceStatus::Complete;
+        }
+        if !is_lead_byte {
+            return SequenceStatus::Invalid;
+        }
+        if bytes.len() == 1 {
+            return SequenceStatus::Incomplete;
+        }
+        if is_trail_byte(bytes[1]) {
+            return SequenceStatus::Complete;
+        }
+        // GB18030 four bytes sequences: lead, digit, lead, digit
+        if !matches!(self, MultiByteCharset::Gb18030) || !bytes[1].is_ascii_digit() {
+            return SequenceStatus::Invalid;
+        }
+        match bytes.len() {
+            2 => SequenceStatus::Incomplete,
+            3 if matches!(bytes[2], 0x81..=0xFE) => SequenceStatus::Incomplete,
+            4 if bytes[3].is_ascii_digit() => SequenceStatus::Complete,
+            _ => SequenceStatus::Invalid,
+        }
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Option<String> {
+        let encoding = match self {
+            MultiByteCharset::ShiftJis => encoding_rs::SHIFT_JIS,
+            MultiByteCharset::Gb18030 => encoding_rs::GB18030,
+            MultiByteCharset::Big5 => encoding_rs::BIG5,
+            MultiByteCharset::EucKr => encoding_rs::EUC_KR,
+        };
+        encoding
+            .decode_without_bom_handling_and_without_replacement(bytes)
+            .map(|decoded| decoded.into_owned())
+    }
+}
+
+impl MultiByteExtractor {
+    /// Consumes the byte at `offset`, `position` is the byte being scanned which differs from
+    /// `offset` while retrying rejected bytes.
+    fn consume_byte(&mut self, position: u64, offset: u64, c: u8) {
+        if self.pending_bytes.is_empty() {
+            self.pending_offset = offset;
+        }
+        self.pending_bytes.push(c);
+        match self.charset.sequence_status(&self.pending_bytes) {
+            SequenceStatus::Incomplete => {}
+            SequenceStatus::Invalid => self.reject_sequence(position),
+            SequenceStatus::Complete => {
+                let decoded = self.charset.decode(&self.pending_bytes).filter(|decoded| {
+                    decoded
+                        .chars()
+                        .all(|c| is_printable_decoded_character(c, &self.character_set))
+                });
+                match decoded {
+                    Some(decoded) => {
+                        self.pending_bytes.clear();
+                        decoded.chars().for_each(|c| {
+                            self.current_string.push(self.pending_offset, offset + 1, c)
+                        });
+                    }
+                    None => self.reject_sequence(position),
+                }
             }
         }
     }
 
-    fn consume(&mut self, offset: u64, c: u8) -> ErrorResult {
-        let is_char_null = c == 0;
-        self.is_last_char_null = Some(is_char_null);
-        if is_char_null {
-            // This is here because big endian is null first
-            if self.current_string.is_empty() {
-                self.offset = Some(offset);
+    /// Ends the current string and retries the pending bytes, starting from the second one.
+    fn reject_sequence(&mut self, position: u64) {
+        let null_terminated = self.pending_bytes == [0];
+        self.current_string.finish(position, Some(null_terminated));
+        let pending_offset = self.pending_offset;
+        let pending_bytes = take(&mut self.pending_bytes);
+        pending_bytes
+            .into_iter()
+            .enumerate()
+            .skip(1)
+            .for_each(|(i, c)| self.consume_byte(position, pending_offset + i as u64, c))
+    }
+}
+
+impl StringsExtractor for MultiByteExtractor {
+    fn consume(&mut self, offset: u64, bytes: &[u8]) {
+        for (i, c) in bytes.iter().enumerate() {
+            let offset = offset + i as u64;
+            self.consume_byte(offset, offset, *c);
+        }
+    }
+
+    fn stop_consume(&mut self) {
+        self.pending_bytes.clear();
+        self.current_string.finish(END_OF_INPUT, None);
+    }
+
+    impl_same_state!();
+}
+
+impl Utf16Extractor {
+    /// Returns false if the code unit can't be part of a string.
+    fn consume_code_unit(&mut self, offset: u64, end_offset: u64, unit: u16) -> bool {
+        let position = end_offset - 1;
+        if let Some((high_surrogate_offset, high_surrogate)) = self.high_surrogate.take() {
+            if is_low_surrogate(unit) {
+                let c = char::decode_utf16([high_surrogate, unit])
+                    .next()
+                    .and_then(|c| c.ok())
+                    .filter(|c| {
+                        is_printable_unicode_character(
+                            *c,
+                            &self.character_set,
+                            &self.unicode_scripts,
+                        )
+                    });
+                return match c {
+                    Some(c) => {
+                        self.current_string
+                            .push(high_surrogate_offset, end_offset, c);
+                        true
+                    }
+                    None => {
+                        self.current_string.finish(position, Some(false));
+                        false
+                    }
+                };
             }
-            return Ok(());
+            // A lone high surrogate ends the string, the current unit may start a new one
+            self.current_string.finish(position, Some(false));
+        }
+        if is_high_surrogate(unit) {
+            self.high_surrogate = Some((offset, unit));
+            return true;
         }
-        if self.is_start_writing {
-            self.writer.borrow_mut().write_char(c as char)?;
-        } else if self.current_string.is_empty() && !self.is_start_writing {
-            if self.offset.is_none() {
-                self.offset = Some(offset);
+        match char::from_u32(unit as u32).filter(|c| {
+            is_printable_unicode_character(*c, &self.character_set, &self.unicode_scripts)
+        }) {
+            Some(c) => {
+                self.current_string.push(offset, end_offset, c);
+                true
             }
-            self.current_string.push(c);
-        } else if self.current_string.len() == self.min_length - 1 && !self.is_start_writing {
-            self.is_start_writing = true;
-            self.current_string.push(c);
-            self.writer
-                .borrow_mut()
-                .start_string_consume(take(&mut self.current_string), self.offset.unwrap())?;
+            None => {
+                self.current_string.finish(position, Some(unit == 0));
+                false
+            }
+        }
+    }
+
+    fn consume_byte(&mut self, offset: u64, c: u8) {
+        let (unit_offset, first_byte) = match self.pending_byte.take() {
+            Some(pending_byte) => pending_byte,
+            None => {
+                self.pending_byte = Some((offset, c));
+                return;
+            }
+        };
+        let unit = match self.is_big_endian {
+            true => u16::from_be_bytes([first_byte, c]),
+            false => u16::from_le_bytes([first_byte, c]),
+        };
+        // Alignment is found by retrying a rejected code unit one byte later
+        if !self.consume_code_unit(unit_offset, offset + 1, unit) {
+            self.pending_byte = Some((offset, c));
+        }
+    }
+}
+
+impl StringsExtractor for Utf16Extractor {
+    fn consume(&mut self, offset: u64, bytes: &[u8]) {
+        for (i, c) in bytes.iter().enumerate() {
+            self.consume_byte(offset + i as u64, *c);
+        }
+    }
+
+    fn stop_consume(&mut self) {
+        self.pending_byte = None;
+        self.high_surrogate = None;
+        self.current_string.finish(END_OF_INPUT, None);
+    }
+
+    impl_same_state!();
+}
+
+impl Utf32Extractor {
+    fn consume_byte(&mut self, offset: u64, c: u8) {
+        if self.pending_bytes.is_empty() {
+            self.pending_offset = offset;
+        }
+        self.pending_bytes.push(c);
+        if self.pending_bytes.len() < 4 {
+            return;
+        }
+        let unit_bytes = [
+            self.pending_bytes[0],
+            self.pending_bytes[1],
+            self.pending_bytes[2],
+            self.pending_bytes[3],
+        ];
+        let unit = match self.is_big_endian {
+            true => u32::from_be_bytes(unit_bytes),
+            false => u32::from_le_bytes(unit_bytes),
+        };
+        match char::from_u32(unit).filter(|c| {
+            is_printable_unicode_character(*c, &self.character_set, &self.unicode_scripts)
+        }) {
+            Some(c) => {
+                self.pending_bytes.clear();
+                self.current_string.push(self.pending_offset, offset + 1, c);
+            }
+            None => {
+                // Alignment is found by retrying a rejected code unit one byte later
+                self.pending_bytes.remove(0);
+                self.pending_offset += 1;
+                self.current_string.finish(offset, Some(unit == 0));
+            }
+        }
+    }
+}
+
+impl StringsExtractor for Utf32Extractor {
+    fn consume(&mut self, offset: u64, bytes: &[u8]) {
+        for (i, c) in bytes.iter().enumerate() {
+            self.consume_byte(offset + i as u64, *c);
+        }
+    }
+
+    fn stop_consume(&mut self) {
+        self.pending_bytes.clear();
+        self.current_string.finish(END_OF_INPUT, None);
+    }
+
+    impl_same_state!();
+}
+
+impl Utf8Extractor {
+    fn consume_start_byte(&mut self, offset: u64, c: u8) {
+        if self.character_set.contains(c) {
+            self.current_string.push(offset, offset + 1, c as char);
+        } else if utf8_sequence_length(c).is_some() {
+            self.pending_offset = offset;
+            self.pending_bytes.push(c);
         } else {
-            self.current_string.push(c);
+            self.current_string.finish(offset, Some(c == 0));
         }
-        Ok(())
     }
 
-    fn stop_consume(&mut self) -> ErrorResult {
-        if self.is_start_writing {
-            self.writer.borrow_mut().finish_string_consume()?;
+    fn consume_byte(&mut self, offset: u64, c: u8) {
+        if self.pending_bytes.is_empty() {
+            return self.consume_start_byte(offset, c);
+        }
+        if !is_utf8_continuation_byte(c) {
+            // The broken sequence ends the string, the current byte may start a new one
+            self.pending_bytes.clear();
+            self.current_string.finish(offset, Some(false));
+            return self.consume_start_byte(offset, c);
+        }
+        self.pending_bytes.push(c);
+        if Some(self.pending_bytes.len()) != utf8_sequence_length(self.pending_bytes[0]) {
+            return;
+        }
+        // Rejects overlong encodings and surrogates
+        let decoded = std::str::from_utf8(&self.pending_bytes)
+            .ok()
+            .and_then(|s| s.chars().next())
+            .filter(|ch| !ch.is_control());
+        self.pending_bytes.clear();
+        match decoded {
+            Some(ch) => self
+                .current_string
+                .push(self.pending_offset, offset + 1, ch),
+            None => self.current_string.finish(offset, Some(false)),
         }
-        self.is_last_char_null = None;
-        self.is_start_writing = false;
-        self.offset = None;
-        self.current_string.clear();
-        Ok(())
     }
 }
+
+impl StringsExtractor for Utf8Extractor {
+    fn consume(&mut self, offset: u64, bytes: &[u8]) {
+        for (i, c) in bytes.iter().enumerate() {
+            self.consume_byte(offset + i as u64, *c);
+        }
+    }
+
+    fn stop_consume(&mut self) {
+        self.pending_bytes.clear();
+        self.current_string.finish(END_OF_INPUT, None);
+    }
+
+    impl_same_state!();
+}
@@ -1,65 +1,73 @@
 use std::io::Write;
 use std::mem::take;
 
+use crate::extracted_string::ExtractedString;
+use crate::files::FileError;
 use crate::ErrorResult;
 
+/// Destination of extracted strings, see [`extract_into`](crate::extract_into).
+///
+/// `write_string` is called once per string, as soon as the string ends, so strings arrive
+/// ordered by their `end_offset`. Strings of different encodings ending at the same byte arrive in
+/// the order of the configured encodings. Strings shorter than the configured minimum length are
+/// never written. An error stops the extraction and is returned to the caller.
 pub trait StringWriter {
-    fn start_string_consume(&mut self, string: Vec<u8>, offset: u64) -> ErrorResult;
-    fn write_char(&mut self, c: char) -> ErrorResult;
-    fn finish_string_consume(&mut self) -> ErrorResult;
+    fn write_string(&mut self, extracted_string: ExtractedString) -> ErrorResult;
+
+    /// Called with a file of a multi-file input that can't be scanned, the next files are
+    /// scanned unless an error is returned. The file is skipped by default.
+    fn write_error(&mut self, file_error: FileError) -> ErrorResult {
+        let _ = file_error;
+        Ok(())
+    }
 }
 
+impl<W> StringWriter for &mut W
+where
+    W: StringWriter + ?Sized,
+{
+    fn write_string(&mut self, extracted_string: ExtractedString) -> ErrorResult {
+        (**self).write_string(extracted_string)
+    }
+
+    fn write_error(&mut self, file_error: FileError) -> ErrorResult {
+        (**self).write_error(file_error)
+    }
+}
+
+/// Collects strings into a `Vec`.
+#[derive(Default)]
 pub struct VectorWriter {
-    vec: Vec<(String, u64)>,
-    current_string: String,
-    current_offset: u64,
+    vec: Vec<ExtractedString>,
 }
 
 impl VectorWriter {
     pub fn new() -> Self {
-        VectorWriter {
-            vec: vec![],
-            current_offset: 0,
-            current_string: String::new(),
-        }
+        VectorWriter { vec: vec![] }
     }
 }
 
 impl StringWriter for VectorWriter {
-    fn start_string_consume(&mut self, string: Vec<u8>, offset: u64) -> ErrorResult {
-        self.current_offset = offset;
-        self.current_string = String::with_capacity(string.len());
-        string
-            .into_iter()
-            .for_each(|c| self.current_string.push(c as char));
-        Ok(())
-    }
-
-    fn write_char(&mut self, c: char) -> ErrorResult {
-        self.current_string.push(c);
-        Ok(())
-    }
-
-    fn finish_string_consume(&mut self) -> ErrorResult {
-        if self.current_string.is_empty() {
-            return Ok(());
-        }
-        let string = take(&mut self.current_string);
-        self.vec.push((string, self.current_offset));
+    fn write_string(&mut self, extracted_string: ExtractedString) -> ErrorResult {
+        self.vec.push(extracted_string);
         Ok(())
     }
 }
 
 impl VectorWriter {
-    pub fn get_strings(&mut self) -> Vec<(String, u64)> {
+    /// Takes the strings collected so far.
+    pub fn get_strings(&mut self) -> Vec<ExtractedString> {
         take(&mut self.vec)
     }
 }
 
+/// Writes strings as a json array, `[["text",offset],...]` or an array of objects with every
+/// field of [`ExtractedString`] when created `with_details`, leaving out the executable fields,
+/// such as the section, of strings that have none. [`JsonWriter::finish`] must be called once the extraction
+/// is done to close the array.
 pub struct JsonWriter<T> {
     writer: T,
-    current_offset: u64,
-    is_start_writing: bool,
+    with_details: bool,
     is_first_element: bool,
 }
 
@@ -67,24 +75,72 @@ impl<T> StringWriter for JsonWriter<T>
 where
     T: Write,
 {
-    fn start_string_consume(&mut self, string: Vec<u8>, offset: u64) -> ErrorResult {
-        self.current_offset = offset;
-        for ch in string.into_iter() {
-            self.write_chars_to_writer(ch)?;
+    fn write_string(&mut self, extracted_string: ExtractedString) -> ErrorResult {
+        match self.is_first_element {
+            true => self.writer.write_all(b"[")?,
+            false => self.writer.write_all(b",")?,
         }
-        Ok(())
-    }
-
-    fn write_char(&mut self, c: char) -> ErrorResult {
-        self.write_chars_to_writer(c as u8)
-    }
-
-    fn finish_string_consume(&mut self) -> ErrorResult {
-        self.writer.write_all(b"\",")?;
-        self.writer
-            .write_all(format!("{}", self.current_offset).as_bytes())?;
-        self.writer.write_all(b"]")?;
-        self.is_start_writing = false;
+        self.is_first_element = false;
+        let text = self.escape_json_string(&extracted_string.text);
+        if !self.with_details {
+            write!(self.writer, "[\"{}\",{}]", text, extracted_string.offset)?;
+            return Ok(());
+        }
+        let null_terminated = match extracted_string.null_terminated {
+            Some(null_terminated) => null_terminated.to_string(),
+            None => String::from("null"),
+        };
+        write!(
+            self.writer,
+            "{{\"text\":\"{}\",\"offset\":{},\"end_offset\":{},\"byte_length\":{},\"encoding\":\"{}\",\"null_terminated\":{}",
+            text,
+            extracted_string.offset,
+            extracted_string.end_offset,
+            extracted_string.byte_length,
+            extracted_string.encoding,
+            null_terminated,
+        )?;
+        if let Some(section) = &extracted_string.section {
+            let section = self.escape_json_string(section);
+            write!(self.writer, ",\"section\":\"{}\"", section)?;
+        }
+        if let Some(virtual_address) = extracted_string.virtual_address {
+            write!(self.writer, ",\"virtual_address\":{}", virtual_address)?;
+        }
+        if let Some(relative_virtual_address) = extracted_string.relative_virtual_address {
+            write!(
+                self.writer,
+                ",\"relative_virtual_address\":{}",
+                relative_virtual_address
+            )?;
+        }
+        if let Some(resource) = &extracted_string.resource {
+            let resource = self.escape_json_string(resource);
+            write!(self.writer, ",\"resource\":\"{}\"", resource)?;
+        }
+        if let Some(architecture) = &extracted_string.architecture {
+            let architecture = self.escape_json_string(architecture);
+            write!(self.writer, ",\"architecture\":\"{}\"", architecture)?;
+        }
+        if let Some(cfstring_address) = extracted_string.cfstring_address {
+            write!(self.writer, ",\"cfstring_address\":{}", cfstring_address)?;
+        }
+        if let Some(mapping) = &extracted_string.mapping {
+            let mapping = self.escape_json_string(mapping);
+            write!(self.writer, ",\"mapping\":\"{}\"", mapping)?;
+        }
+        if let Some(file_name) = &extracted_string.file_name {
+            let file_name = self.escape_json_string(file_name);
+            write!(self.writer, ",\"file_name\":\"{}\"", file_name)?;
+        }
+        if let Some(member) = &extracted_string.member {
+            let member = self.escape_json_string(member);
+            write!(self.writer, ",\"member\":\"{}\"", member)?;
+        }
+        if let Some(compression) = extracted_string.compression {
+            write!(self.writer, ",\"compression\":\"{}\"", compression)?;
+        }
+        self.writer.write_all(b"}")?;
         Ok(())
     }
 }
@@ -96,42 +152,42 @@ where
     pub fn new(writer: T) -> Self {
         JsonWriter {
             writer,
-            current_offset: 0,
-            is_start_writing: false,
+            with_details: false,
             is_first_element: true,
         }
     }
 
+    /// Write every string as an object with all of its fields instead of a `[text, offset]` pair.
+    pub fn with_details(mut self, with_details: bool) -> Self {
+        self.with_details = with_details;
+        self
+    }
+
+    /// Closes the json array.
     pub fn finish(&mut self) -> ErrorResult {
+        if self.is_first_element {
+            self.writer.write_all(b"[")?;
+        }
         self.writer.write_all(b"]")?;
         Ok(())
     }
 
-    fn write_chars_to_writer(&mut self, c: u8) -> ErrorResult {
-        if !self.is_start_writing {
-            self.is_start_writing = true;
-            if self.is_first_element {
-                // Start writing the first element, needs to write `[["`
-                self.writer.write_all(b"[[\"")?;
-                self.is_first_element = false;
-            } else {
-                // Start writing current string, needs to write `,["`
-                self.writer.write_all(b",[\"")?;
-            }
-        }
-        let v = self.escape_json_character(c);
-        self.writer.write_all(&v)?;
-        Ok(())
+    fn escape_json_string(&self, string: &str) -> String {
+        string
+            .chars()
+            .map(|c| self.escape_json_character(c))
+            .collect()
     }
 
-    fn escape_json_character(&self, c: u8) -> Vec<u8> {
-        match c as char {
-            '\n' => b"\\n".to_vec(),
-            '\t' => b"\\t".to_vec(),
-            '\r' => b"\\r".to_vec(),
-            '"' => b"\\\"".to_vec(),
-            '\\' => b"\\\\".to_vec(),
-            _ => vec![c],
+    fn escape_json_character(&self, c: char) -> String {
+        match c {
+            '\n' => String::from("\\n"),
+            '\t' => String::from("\\t"),
+            '\r' => String::from("\\r"),
+            '"' => String::from("\\\""),
+            '\\' => String::from("\\\\"),
+            _ if c.is_control() => format!("\\u{:04x}", c as u32),
+            _ => c.to_string(),
         }
     }
 }
@@ -0,0 +1,113 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// Unicode scripts (and script-like categories) that wide-character extractors may accept
+/// on top of printable ASCII.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum UnicodeScript {
+    Latin,
+    Greek,
+    Cyrillic,
+    Armenian,
+    Hebrew,
+    Arabic,
+    Devanagari,
+    Thai,
+    Hangul,
+    Kana,
+    Han,
+    /// General punctuation, currency and letterlike symbols.
+    Punctuation,
+    Emoji,
+}
+
+const CJK_PUNCTUATION: RangeInclusive<u32> = 0x3000..=0x303F;
+const FULLWIDTH_FORMS: RangeInclusive<u32> = 0xFF00..=0xFFEF;
+
+impl UnicodeScript {
+    fn ranges(&self) -> &'static [RangeInclusive<u32>] {
+        match self {
+            UnicodeScript::Latin => &[0x00A0..=0x024F, 0x1E00..=0x1EFF],
+            UnicodeScript::Greek => &[0x0370..=0x03FF, 0x1F00..=0x1FFF],
+            UnicodeScript::Cyrillic => &[0x0400..=0x052F],
+            UnicodeScript::Armenian => &[0x0530..=0x058F],
+            UnicodeScript::Hebrew => &[0x0590..=0x05FF],
+            UnicodeScript::Arabic => &[0x0600..=0x06FF, 0x0750..=0x077F],
+            UnicodeScript::Devanagari => &[0x0900..=0x097F],
+            UnicodeScript::Thai => &[0x0E00..=0x0E7F],
+            UnicodeScript::Hangul => &[
+                0x1100..=0x11FF,
+                0x3130..=0x318F,
+                0xAC00..=0xD7A3,
+                CJK_PUNCTUATION,
+            ],
+            UnicodeScript::Kana => &[0x3040..=0x30FF, CJK_PUNCTUATION, FULLWIDTH_FORMS],
+            UnicodeScript::Han => &[
+                0x3400..=0x4DBF,
+                0x4E00..=0x9FFF,
+                0xF900..=0xFAFF,
+                0x20000..=0x2FA1F,
+                CJK_PUNCTUATION,
+                FULLWIDTH_FORMS,
+            ],
+            UnicodeScript::Punctuation => &[0x2000..=0x206F, 0x20A0..=0x20CF, 0x2100..=0x214F],
+            UnicodeScript::Emoji => &[0x2600..=0x27BF, 0x1F000..=0x1FAFF],
+        }
+    }
+
+    pub fn contains(&self, c: char) -> bool {
+        let c = c as u32;
+        self.ranges().iter().any(|range| range.contains(&c))
+    }
+}
+
+impl fmt::Display for UnicodeScript {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug)]
+pub struct UnicodeScriptNotFoundError {
+    script: String,
+}
+
+impl fmt::Display for UnicodeScriptNotFoundError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unicode script not found: {:?}", self.script)
+    }
+}
+
+impl UnicodeScriptNotFoundError {
+    fn new(script: String) -> Self {
+        UnicodeScriptNotFoundError { script }
+    }
+}
+
+impl Error for UnicodeScriptNotFoundError {}
+
+impl FromStr for UnicodeScript {
+    type Err = UnicodeScriptNotFoundError;
+
+    fn from_str(script: &str) -> Result<Self, Self::Err> {
+        let script: &str = &script.to_lowercase();
+        match script {
+            "latin" => Ok(UnicodeScript::Latin),
+            "greek" => Ok(UnicodeScript::Greek),
+            "cyrillic" => Ok(UnicodeScript::Cyrillic),
+            "armenian" => Ok(UnicodeScript::Armenian),
+            "hebrew" => Ok(UnicodeScript::Hebrew),
+            "arabic" => Ok(UnicodeScript::Arabic),
+            "devanagari" => Ok(UnicodeScript::Devanagari),
+            "thai" => Ok(UnicodeScript::Thai),
+            "hangul" => Ok(UnicodeScript::Hangul),
+            "kana" => Ok(UnicodeScript::Kana),
+            "han" => Ok(UnicodeScript::Han),
+            "punctuation" => Ok(UnicodeScript::Punctuation),
+            "emoji" => Ok(UnicodeScript::Emoji),
+            _ => Err(UnicodeScriptNotFoundError::new(script.to_owned())),
+        }
+    }
+}
@@ -1,6 +1,17 @@
-use rust_strings::{dump_strings, strings, BytesConfig, Encoding, FileConfig};
-use std::io::{Read, Write};
-use tempfile::NamedTempFile;
+#[cfg(target_os = "linux")]
+use rust_strings::ProcessConfig;
+use rust_strings::{
+    dump_extracted_strings, dump_strings, extract_into, extract_strings, strings, strings_iter,
+    BinaryFormat, BytesConfig, CharacterSet, Compression, Decompression, DirectoryConfig, Encoding,
+    ExtractedString, FileConfig, FileError, MaxLengthPolicy, ReaderConfig, StringWriter,
+    UnicodeScript,
+};
+use std::error::Error;
+use std::fs;
+use std::io::{Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tempfile::{tempdir, NamedTempFile};
 
 #[test]
 fn test_bytes_config() {
@@ -119,3 +130,1980 @@ fn test_json_dump_multiple_strings() {
         String::from("[[\"test\\\"\\n\\tmore\",2],[\"more text over here\",15]]")
     );
 }
+
+#[test]
+fn test_utf8() {
+    let config =
+        BytesConfig::new("\x00héllo wörld\x00".as_bytes().to_vec()).with_encoding(Encoding::UTF8);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(vec![(String::from("héllo wörld"), 1)], extracted);
+}
+
+#[test]
+fn test_utf8_min_length_counts_characters() {
+    let config = BytesConfig::new("\x00日本\x00日本語\x00".as_bytes().to_vec())
+        .with_encoding(Encoding::UTF8);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(vec![(String::from("日本語"), 8)], extracted);
+}
+
+#[test]
+fn test_utf8_invalid_sequence() {
+    let config =
+        BytesConfig::new(b"abc\xe6\x97test\xc0\xafmore".to_vec()).with_encoding(Encoding::UTF8);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(
+        vec![
+            (String::from("abc"), 0),
+            (String::from("test"), 5),
+            (String::from("more"), 11)
+        ],
+        extracted
+    );
+}
+
+#[test]
+fn test_utf8_json_dump() {
+    let file = NamedTempFile::new().unwrap();
+    let config =
+        BytesConfig::new("\x00\"ñandú\"\x00".as_bytes().to_vec()).with_encoding(Encoding::UTF8);
+
+    let path = file.path().to_path_buf();
+    dump_strings(&config, path).unwrap();
+    let mut string = String::new();
+    file.as_file().read_to_string(&mut string).unwrap();
+    assert_eq!(string, String::from("[[\"\\\"ñandú\\\"\",1]]"));
+}
+
+fn encode_utf16(string: &str, is_big_endian: bool) -> Vec<u8> {
+    string
+        .encode_utf16()
+        .flat_map(|unit| match is_big_endian {
+            true => unit.to_be_bytes(),
+            false => unit.to_le_bytes(),
+        })
+        .collect()
+}
+
+#[test]
+fn test_utf16le_unicode_scripts() {
+    let mut bytes = b"\x01\x02".to_vec();
+    bytes.extend(encode_utf16("Привет, мир", false));
+    bytes.extend(b"\x00\x00");
+    let config = BytesConfig::new(bytes.clone()).with_encoding(Encoding::UTF16LE);
+    let extracted = strings(&config).unwrap();
+    assert!(extracted.is_empty());
+
+    let config = BytesConfig::new(bytes)
+        .with_encoding(Encoding::UTF16LE)
+        .with_unicode_scripts(vec![UnicodeScript::Cyrillic]);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(vec![(String::from("Привет, мир"), 2)], extracted);
+}
+
+#[test]
+fn test_utf16be_surrogate_pairs() {
+    let mut bytes = b"\xff".to_vec();
+    bytes.extend(encode_utf16("shalom שלום 🙂!", true));
+    let config = BytesConfig::new(bytes)
+        .with_encoding(Encoding::UTF16BE)
+        .with_unicode_scripts(vec![UnicodeScript::Hebrew, UnicodeScript::Emoji]);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(vec![(String::from("shalom שלום 🙂!"), 1)], extracted);
+}
+
+#[test]
+fn test_utf16le_lone_surrogate() {
+    let mut bytes = encode_utf16("abc", false);
+    bytes.extend(b"\x00\xd8");
+    bytes.extend(encode_utf16("def", false));
+    let config = BytesConfig::new(bytes)
+        .with_encoding(Encoding::UTF16LE)
+        .with_unicode_scripts(vec![UnicodeScript::Emoji]);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(
+        vec![(String::from("abc"), 0), (String::from("def"), 8)],
+        extracted
+    );
+}
+
+#[test]
+fn test_utf32le() {
+    let config = BytesConfig::new(
+        b"\x01t\x00\x00\x00e\x00\x00\x00s\x00\x00\x00t\x00\x00\x00\x00\x00\x00\x00".to_vec(),
+    )
+    .with_encoding(Encoding::UTF32LE);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(vec![(String::from("test"), 1)], extracted);
+}
+
+#[test]
+fn test_utf32be() {
+    let bytes: Vec<u8> = "wide ✓ chars"
+        .chars()
+        .flat_map(|c| (c as u32).to_be_bytes())
+        .collect();
+    let config = BytesConfig::new(bytes.clone()).with_encoding(Encoding::UTF32BE);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(
+        vec![(String::from("wide "), 0), (String::from(" chars"), 24)],
+        extracted
+    );
+
+    let config = BytesConfig::new(bytes)
+        .with_encoding(Encoding::UTF32BE)
+        .with_unicode_scripts(vec![UnicodeScript::Emoji]);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(vec![(String::from("wide ✓ chars"), 0)], extracted);
+}
+
+#[test]
+fn test_code_pages() {
+    let bytes = b"\x00caf\xe9 cr\xe8me\x00\x80uro\x00".to_vec();
+    let config = BytesConfig::new(bytes.clone()).with_encoding(Encoding::LATIN1);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(
+        vec![(String::from("café crème"), 1), (String::from("uro"), 13)],
+        extracted
+    );
+
+    let config = BytesConfig::new(bytes).with_encoding(Encoding::CP1252);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(
+        vec![(String::from("café crème"), 1), (String::from("€uro"), 12)],
+        extracted
+    );
+}
+
+#[test]
+fn test_koi8r() {
+    let config = BytesConfig::new(b"\x01\xf0\xd2\xc9\xd7\xc5\xd4!\x01".to_vec())
+        .with_encoding(Encoding::KOI8R);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(vec![(String::from("Привет!"), 1)], extracted);
+}
+
+#[test]
+fn test_cp437() {
+    let config = BytesConfig::new(b"\xc9\xcd\xcd\xbb\x00".to_vec()).with_encoding(Encoding::CP437);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(vec![(String::from("╔══╗"), 0)], extracted);
+}
+
+#[test]
+fn test_ebcdic() {
+    let bytes = b"\x00\x00\xc8\xc5\xd3\xd3\xd6k@\xe6\x96\x99\x93\x84Z@\xba\xa7\xbb\x00".to_vec();
+    let config = BytesConfig::new(bytes.clone()).with_encoding(Encoding::CP037);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(vec![(String::from("HELLO, World! [x]"), 2)], extracted);
+
+    let config = BytesConfig::new(bytes).with_encoding(Encoding::CP1047);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(vec![(String::from("HELLO, World! Ýx¨"), 2)], extracted);
+}
+
+#[test]
+fn test_shift_jis() {
+    let config = BytesConfig::new(b"\x01\x93\xfa\x96{\x8c\xea\x83e\x83L\x83X\x83g\xff".to_vec())
+        .with_encoding(Encoding::SHIFTJIS);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(vec![(String::from("日本語テキスト"), 1)], extracted);
+}
+
+#[test]
+fn test_gb18030() {
+    let config = BytesConfig::new(b"\x00\xd6\xd0\xce\xc4\x952\x826\x00\xd6\xd0\xce".to_vec())
+        .with_encoding(Encoding::GB18030);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(vec![(String::from("中文𠀀"), 1)], extracted);
+}
+
+#[test]
+fn test_big5_and_euc_kr() {
+    let config = BytesConfig::new(b"\xa4\xa4\xa4\xe5\xa6r\xb2\xc5\xa6\xea".to_vec())
+        .with_encoding(Encoding::BIG5);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(vec![(String::from("中文字符串"), 0)], extracted);
+
+    let config =
+        BytesConfig::new(b"\xc7\xd1\xb1\xb9\xbe\xee \xb9\xae\xc0\xda\xbf\xad\x80".to_vec())
+            .with_encoding(Encoding::EUCKR);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(vec![(String::from("한국어 문자열"), 0)], extracted);
+}
+
+#[test]
+fn test_multi_byte_min_length_counts_characters() {
+    let config = BytesConfig::new(b"\x00\xd6\xd0\xce\xc4\x00\xd6\xd0\xce\xc4\xd7\xd6\x00".to_vec())
+        .with_encoding(Encoding::GB18030);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(vec![(String::from("中文字"), 6)], extracted);
+}
+
+#[test]
+fn test_character_set_gnu_default() {
+    let config = BytesConfig::new(b"first\nsecond\tline\x00".to_vec())
+        .with_character_set(CharacterSet::gnu_default());
+    let extracted = strings(&config).unwrap();
+    assert_eq!(
+        vec![
+            (String::from("first"), 0),
+            (String::from("second\tline"), 6)
+        ],
+        extracted
+    );
+}
+
+#[test]
+fn test_character_set_alphanumeric() {
+    let config = BytesConfig::new(
+        "abc-de_fgh1 ij"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect(),
+    )
+    .with_encoding(Encoding::UTF16LE)
+    .with_character_set(CharacterSet::alphanumeric());
+    let extracted = strings(&config).unwrap();
+    assert_eq!(
+        vec![(String::from("abc"), 0), (String::from("fgh1"), 14)],
+        extracted
+    );
+}
+
+#[test]
+fn test_character_set_custom_char() {
+    let config = BytesConfig::new(b"\x00\x1b[31mred\x00".to_vec())
+        .with_character_set(CharacterSet::gnu_default().with_char(0x1b));
+    let extracted = strings(&config).unwrap();
+    assert_eq!(vec![(String::from("\x1b[31mred"), 1)], extracted);
+}
+
+#[test]
+fn test_json_dump_control_characters() {
+    let file = NamedTempFile::new().unwrap();
+    let config = BytesConfig::new(b"\x1b[0m\x00".to_vec())
+        .with_character_set(CharacterSet::gnu_default().with_char(0x1b));
+
+    let path = file.path().to_path_buf();
+    dump_strings(&config, path).unwrap();
+    let mut string = String::new();
+    file.as_file().read_to_string(&mut string).unwrap();
+    assert_eq!(string, String::from("[[\"\\u001b[0m\",0]]"));
+}
+
+/// The location and encoding of an extracted string, which has no other field set when
+/// scanning raw bytes.
+fn located(extracted_string: &ExtractedString) -> (String, u64, u64, u64, Encoding, Option<bool>) {
+    assert_eq!(extracted_string.section, None);
+    assert_eq!(extracted_string.file_name, None);
+    assert_eq!(extracted_string.member, None);
+    assert_eq!(extracted_string.compression, None);
+    (
+        extracted_string.text.clone(),
+        extracted_string.offset,
+        extracted_string.end_offset,
+        extracted_string.byte_length,
+        extracted_string.encoding,
+        extracted_string.null_terminated,
+    )
+}
+
+#[test]
+fn test_strings_iter() {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(b"first\x01s\x00e\x00c\x00o\x00n\x00d\x00\x00\x00third")
+        .unwrap();
+
+    let path = file.path();
+    let config = FileConfig::new(path)
+        .with_buffer_size(4)
+        .with_encoding(Encoding::ASCII)
+        .with_encoding(Encoding::UTF16LE);
+    let extracted: Vec<ExtractedString> = strings_iter(&config).map(|s| s.unwrap()).collect();
+    assert_eq!(
+        vec![
+            (String::from("first"), 0, 5, 5, Encoding::ASCII, Some(false)),
+            (
+                String::from("second"),
+                6,
+                18,
+                12,
+                Encoding::UTF16LE,
+                Some(true)
+            ),
+            (String::from("third"), 20, 25, 5, Encoding::ASCII, None),
+        ],
+        extracted.iter().map(located).collect::<Vec<_>>()
+    );
+    let extracted: Vec<(String, u64)> = extracted.into_iter().map(|s| (s.text, s.offset)).collect();
+    assert_eq!(strings(&config).unwrap(), extracted);
+}
+
+#[test]
+fn test_strings_iter_is_lazy() {
+    let config = BytesConfig::new(b"first\x00second\x00".to_vec());
+    let mut extracted = strings_iter(&config);
+    assert_eq!(
+        String::from("first"),
+        extracted.next().unwrap().unwrap().text
+    );
+    assert_eq!(
+        String::from("second"),
+        extracted.next().unwrap().unwrap().text
+    );
+    assert!(extracted.next().is_none());
+}
+
+#[test]
+fn test_strings_iter_missing_file() {
+    let config = FileConfig::new(Path::new("/does/not/exist"));
+    let mut extracted = strings_iter(&config);
+    assert!(extracted.next().unwrap().is_err());
+    assert!(extracted.next().is_none());
+}
+
+#[test]
+fn test_extract_strings() {
+    let config = BytesConfig::new(b"\xffascii\x00\xe2\x82\xacuro\xff".to_vec())
+        .with_encoding(Encoding::UTF8);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!(
+        vec![
+            (String::from("ascii"), 1, 6, 5, Encoding::UTF8, Some(true)),
+            (
+                String::from("\u{20ac}uro"),
+                7,
+                13,
+                6,
+                Encoding::UTF8,
+                Some(false)
+            ),
+        ],
+        extracted.iter().map(located).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_extract_strings_end_of_input() {
+    let config =
+        BytesConfig::new(b"t\x00e\x00s\x00t\x00".to_vec()).with_encoding(Encoding::UTF16LE);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!(1, extracted.len());
+    assert_eq!(8, extracted[0].byte_length);
+    assert_eq!(4, extracted[0].text.chars().count());
+    assert_eq!(None, extracted[0].null_terminated);
+}
+
+#[test]
+fn test_json_dump_extracted_strings() {
+    let file = NamedTempFile::new().unwrap();
+    let config = BytesConfig::new(b"test\x00".to_vec());
+    let path = file.path().to_path_buf();
+    dump_extracted_strings(&config, path).unwrap();
+    let mut string = String::new();
+    file.as_file().read_to_string(&mut string).unwrap();
+    assert_eq!(
+        string,
+        String::from(
+            "[{\"text\":\"test\",\"offset\":0,\"end_offset\":4,\"byte_length\":4,\"encoding\":\"ASCII\",\"null_terminated\":true}]"
+        )
+    );
+}
+
+#[test]
+fn test_json_dump_no_strings() {
+    let file = NamedTempFile::new().unwrap();
+    let config = BytesConfig::new(b"\x00\x00".to_vec());
+    let path = file.path().to_path_buf();
+    dump_strings(&config, path).unwrap();
+    let mut string = String::new();
+    file.as_file().read_to_string(&mut string).unwrap();
+    assert_eq!(string, String::from("[]"));
+}
+
+struct LimitWriter {
+    texts: Vec<String>,
+    limit: usize,
+}
+
+impl StringWriter for LimitWriter {
+    fn write_string(&mut self, extracted_string: ExtractedString) -> Result<(), Box<dyn Error>> {
+        if self.texts.len() == self.limit {
+            return Err("limit reached".into());
+        }
+        self.texts.push(extracted_string.text);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_extract_into() {
+    let config = BytesConfig::new(b"first\x00second\x00third".to_vec());
+    let mut writer = LimitWriter {
+        texts: vec![],
+        limit: 3,
+    };
+    extract_into(&config, &mut writer).unwrap();
+    assert_eq!(vec!["first", "second", "third"], writer.texts);
+}
+
+#[test]
+fn test_extract_into_writer_error() {
+    let config = BytesConfig::new(b"first\x00second\x00third".to_vec());
+    let mut writer = LimitWriter {
+        texts: vec![],
+        limit: 1,
+    };
+    let err = extract_into(&config, &mut writer).unwrap_err();
+    assert_eq!("limit reached", err.to_string());
+    assert_eq!(vec!["first"], writer.texts);
+}
+
+#[test]
+fn test_reader_config() {
+    let reader = Cursor::new(b"fi".to_vec()).chain(Cursor::new(b"rst\x01t\x00w\x00o\x00".to_vec()));
+    let config = ReaderConfig::new(reader)
+        .with_buffer_size(2)
+        .with_encodings(vec![Encoding::ASCII, Encoding::UTF16LE]);
+    let extracted = strings(&config).unwrap();
+    assert_eq!(
+        vec![(String::from("first"), 0), (String::from("two"), 6)],
+        extracted
+    );
+}
+
+#[test]
+fn test_reader_config_is_consumed() {
+    let config = ReaderConfig::new(Cursor::new(b"test\x00".to_vec()));
+    assert_eq!(1, strings(&config).unwrap().len());
+    assert!(strings(&config).unwrap().is_empty());
+    assert_eq!(5, config.into_inner().position());
+}
+
+#[test]
+fn test_file_config_mmap() {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(b"first\x00second\x00t\x00h\x00i\x00r\x00d\x00")
+        .unwrap();
+    let config = FileConfig::new(file.path())
+        .with_buffer_size(4)
+        .with_encodings(vec![Encoding::ASCII, Encoding::UTF16LE]);
+    let buffered = strings(&config).unwrap();
+    let mapped = strings(&config.with_mmap(true)).unwrap();
+    assert_eq!(3, mapped.len());
+    assert_eq!(buffered, mapped);
+}
+
+#[test]
+fn test_file_config_mmap_fallback() {
+    let file = NamedTempFile::new().unwrap();
+    let config = FileConfig::new(file.path()).with_mmap(true);
+    assert!(strings(&config).unwrap().is_empty());
+    if Path::new("/dev/null").exists() {
+        let config = FileConfig::new(Path::new("/dev/null")).with_mmap(true);
+        assert!(strings(&config).unwrap().is_empty());
+    }
+}
+
+#[test]
+fn test_buffer_size_does_not_change_strings() {
+    let mut file = NamedTempFile::new().unwrap();
+    let bytes: Vec<u8> = (0..4096u32)
+        .map(|i| (i.wrapping_mul(2654435761) >> 13) as u8 & 0x7f)
+        .collect();
+    file.write_all(&bytes).unwrap();
+    let config = FileConfig::new(file.path())
+        .with_min_length(2)
+        .with_encodings(vec![Encoding::ASCII, Encoding::UTF16LE, Encoding::UTF8]);
+    let expected = extract_strings(&config).unwrap();
+    assert!(!expected.is_empty());
+    for buffer_size in [1, 3, 7, 64] {
+        let config = FileConfig::new(file.path())
+            .with_min_length(2)
+            .with_buffer_size(buffer_size)
+            .with_encodings(vec![Encoding::ASCII, Encoding::UTF16LE, Encoding::UTF8]);
+        assert_eq!(expected, extract_strings(&config).unwrap());
+    }
+}
+
+#[test]
+fn test_threads() {
+    let mut file = NamedTempFile::new().unwrap();
+    // Strings of every encoding crossing buffer boundaries
+    let mut bytes = vec![];
+    for i in 0..64u32 {
+        bytes.extend_from_slice(b"ascii string");
+        bytes.extend(encode_utf16("wide string", false));
+        bytes.extend_from_slice("\u{20ac}uro".as_bytes());
+        bytes.extend((0..i % 7).map(|j| (i.wrapping_mul(2654435761) >> j) as u8));
+    }
+    file.write_all(&bytes).unwrap();
+    let encodings = vec![
+        Encoding::ASCII,
+        Encoding::UTF16LE,
+        Encoding::UTF16BE,
+        Encoding::UTF8,
+        Encoding::UTF32LE,
+        Encoding::SHIFTJIS,
+    ];
+    let config = FileConfig::new(file.path()).with_encodings(encodings.clone());
+    let expected = extract_strings(&config).unwrap();
+    for buffer_size in [1, 2, 3, 5, 16, 100, 1000] {
+        let config = FileConfig::new(file.path())
+            .with_encodings(encodings.clone())
+            .with_buffer_size(buffer_size)
+            .with_threads(4);
+        assert_eq!(expected, extract_strings(&config).unwrap());
+        assert_eq!(expected, extract_strings(&config.with_mmap(true)).unwrap());
+    }
+}
+
+#[test]
+fn test_threads_bytes_config() {
+    let config = BytesConfig::new(b"first\x00second".to_vec()).with_threads(0);
+    assert_eq!(
+        vec![(String::from("first"), 0), (String::from("second"), 6)],
+        strings(&config).unwrap()
+    );
+}
+
+#[test]
+fn test_range() {
+    let bytes = b"first\x00second\x00third\x00".to_vec();
+    let expected = vec![(String::from("ond"), 9), (String::from("thi"), 13)];
+    let config = BytesConfig::new(bytes.clone()).with_range(9, 7);
+    assert_eq!(expected, strings(&config).unwrap());
+    let config = ReaderConfig::new(Cursor::new(bytes.clone()))
+        .with_start_offset(9)
+        .with_end_offset(16);
+    assert_eq!(expected, strings(&config).unwrap());
+
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(&bytes).unwrap();
+    let config = FileConfig::new(file.path())
+        .with_buffer_size(2)
+        .with_range(9, 7);
+    assert_eq!(expected, strings(&config).unwrap());
+    assert_eq!(expected, strings(&config.with_mmap(true)).unwrap());
+}
+
+#[test]
+fn test_range_out_of_input() {
+    let config = BytesConfig::new(b"test".to_vec()).with_range(10, 10);
+    assert!(strings(&config).unwrap().is_empty());
+    let config = BytesConfig::new(b"test".to_vec())
+        .with_start_offset(3)
+        .with_end_offset(1);
+    assert!(strings(&config).unwrap().is_empty());
+}
+
+#[test]
+fn test_max_length_truncate() {
+    let config = BytesConfig::new(b"0123456789\x00short\x00".to_vec())
+        .with_max_length(6, MaxLengthPolicy::Truncate);
+    let extracted = extract_strings(&config).unwrap();
+    let texts: Vec<(&str, u64, u64)> = extracted
+        .iter()
+        .map(|s| (s.text.as_str(), s.offset, s.end_offset))
+        .collect();
+    assert_eq!(vec![("012345...", 0, 10), ("short", 11, 16)], texts);
+    assert_eq!(10, extracted[0].byte_length);
+    assert_eq!(Some(true), extracted[0].null_terminated);
+}
+
+#[test]
+fn test_max_length_drop() {
+    let bytes = [
+        b"0123456789\x00short\x00".to_vec(),
+        encode_utf16("wide string", false),
+    ]
+    .concat();
+    let config = BytesConfig::new(bytes)
+        .with_encodings(vec![Encoding::ASCII, Encoding::UTF16LE])
+        .with_max_length(6, MaxLengthPolicy::Drop);
+    assert_eq!(vec![(String::from("short"), 11)], strings(&config).unwrap());
+}
+
+#[test]
+fn test_max_length_split() {
+    let bytes = [
+        b"0123456789\x00\x00".to_vec(),
+        encode_utf16("wide string", false),
+    ]
+    .concat();
+    let config = BytesConfig::new(bytes)
+        .with_min_length(3)
+        .with_encodings(vec![Encoding::ASCII, Encoding::UTF16LE])
+        .with_max_length(4, MaxLengthPolicy::Split);
+    let extracted = extract_strings(&config).unwrap();
+    let chunks: Vec<(&str, u64, u64, Option<bool>)> = extracted
+        .iter()
+        .map(|s| (s.text.as_str(), s.offset, s.end_offset, s.null_terminated))
+        .collect();
+    // The last chunks are shorter than the minimum length
+    assert_eq!(
+        vec![
+            ("0123", 0, 4, Some(false)),
+            ("4567", 4, 8, Some(false)),
+            ("89", 8, 10, Some(true)),
+            ("wide", 12, 20, Some(false)),
+            (" str", 20, 28, Some(false)),
+            ("ing", 28, 34, None),
+        ],
+        chunks
+    );
+}
+
+#[test]
+fn test_max_length_below_min_length() {
+    let config = BytesConfig::new(b"0123456789\x00ab\x00".to_vec())
+        .with_min_length(5)
+        .with_max_length(2, MaxLengthPolicy::Split);
+    assert_eq!(
+        vec![(String::from("01234"), 0), (String::from("56789"), 5)],
+        strings(&config).unwrap()
+    );
+}
+
+#[test]
+fn test_max_length_threads() {
+    let mut file = NamedTempFile::new().unwrap();
+    let mut bytes = vec![];
+    for i in 0..64usize {
+        bytes.extend(vec![b'a'; i * 3]);
+        bytes.extend(encode_utf16(&"w".repeat(i), false));
+        bytes.push(0);
+    }
+    file.write_all(&bytes).unwrap();
+    for policy in [
+        MaxLengthPolicy::Drop,
+        MaxLengthPolicy::Truncate,
+        MaxLengthPolicy::Split,
+    ] {
+        let config = FileConfig::new(file.path())
+            .with_encodings(vec![Encoding::ASCII, Encoding::UTF16LE])
+            .with_max_length(10, policy);
+        let expected = extract_strings(&config).unwrap();
+        assert!(expected.iter().all(|s| s.text.chars().count() <= 13));
+        for buffer_size in [1, 3, 16, 100] {
+            let config = FileConfig::new(file.path())
+                .with_encodings(vec![Encoding::ASCII, Encoding::UTF16LE])
+                .with_max_length(10, policy)
+                .with_buffer_size(buffer_size)
+                .with_threads(4);
+            assert_eq!(expected, extract_strings(&config).unwrap());
+        }
+    }
+}
+
+#[test]
+fn test_max_length_split_encodings() {
+    let mut file = NamedTempFile::new().unwrap();
+    // A chunk of UTF-8 is split before a multi-byte character that ends an ASCII string
+    let mut bytes = vec![];
+    for i in 0..64usize {
+        bytes.extend(b"abc".repeat(i % 4));
+        bytes.extend("\u{e9}".repeat(i % 3).as_bytes());
+        bytes.extend(encode_utf16(&"w".repeat(i % 5), false));
+        bytes.push((i % 2) as u8);
+    }
+    file.write_all(&bytes).unwrap();
+    let encodings = vec![Encoding::UTF8, Encoding::UTF16LE, Encoding::ASCII];
+    let config = FileConfig::new(file.path())
+        .with_min_length(2)
+        .with_encodings(encodings.clone())
+        .with_max_length(3, MaxLengthPolicy::Split);
+    let expected = extract_strings(&config).unwrap();
+    assert!(expected.iter().any(|s| s.null_terminated == Some(false)));
+    for buffer_size in [1, 2, 3, 7, 64] {
+        for threads in [1, 4] {
+            let config = FileConfig::new(file.path())
+                .with_min_length(2)
+                .with_encodings(encodings.clone())
+                .with_max_length(3, MaxLengthPolicy::Split)
+                .with_buffer_size(buffer_size)
+                .with_threads(threads);
+            assert_eq!(expected, extract_strings(&config).unwrap());
+            let iterated: Vec<ExtractedString> =
+                strings_iter(&config).map(|s| s.unwrap()).collect();
+            assert_eq!(expected, iterated);
+        }
+    }
+}
+
+#[test]
+fn test_null_terminated_only() {
+    let bytes = [
+        b"first\x00second\x01third\x00\x00".to_vec(),
+        encode_utf16("wide\x00", false),
+        encode_utf16("unterminated", false),
+    ]
+    .concat();
+    let config = BytesConfig::new(bytes)
+        .with_encodings(vec![Encoding::ASCII, Encoding::UTF16LE])
+        .with_null_terminated_only(true);
+    let extracted = extract_strings(&config).unwrap();
+    let texts: Vec<(&str, u64, Encoding)> = extracted
+        .iter()
+        .map(|s| (s.text.as_str(), s.offset, s.encoding))
+        .collect();
+    assert_eq!(
+        vec![
+            ("first", 0, Encoding::ASCII),
+            ("third", 13, Encoding::ASCII),
+            ("wide", 20, Encoding::UTF16LE),
+        ],
+        texts
+    );
+    assert!(extracted.iter().all(|s| s.null_terminated == Some(true)));
+}
+
+#[test]
+fn test_null_terminated_only_split() {
+    let config = BytesConfig::new(b"abcdefghij\x00klmnopq\x01".to_vec())
+        .with_null_terminated_only(true)
+        .with_max_length(4, MaxLengthPolicy::Split);
+    let extracted = extract_strings(&config).unwrap();
+    let chunks: Vec<(&str, u64, Option<bool>)> = extracted
+        .iter()
+        .map(|s| (s.text.as_str(), s.offset, s.null_terminated))
+        .collect();
+    assert_eq!(
+        vec![
+            ("abcd", 0, Some(false)),
+            ("efgh", 4, Some(false)),
+            ("ij", 8, Some(true)),
+            // Chunks are extracted as they are split, the last one is skipped without a terminator
+            ("klmn", 11, Some(false)),
+        ],
+        chunks
+    );
+
+    let mut file = NamedTempFile::new().unwrap();
+    let mut bytes = vec![];
+    for i in 0..64usize {
+        bytes.extend(vec![b'a'; i]);
+        bytes.extend(encode_utf16(&"w".repeat(i % 9), false));
+        bytes.push((i % 2) as u8);
+    }
+    file.write_all(&bytes).unwrap();
+    let config = FileConfig::new(file.path())
+        .with_encodings(vec![Encoding::ASCII, Encoding::UTF16LE])
+        .with_null_terminated_only(true)
+        .with_max_length(4, MaxLengthPolicy::Split);
+    let expected = extract_strings(&config).unwrap();
+    assert!(expected.iter().any(|s| s.null_terminated == Some(false)));
+    for buffer_size in [1, 3, 16, 100] {
+        let config = config.clone().with_buffer_size(buffer_size).with_threads(4);
+        assert_eq!(expected, extract_strings(&config).unwrap());
+        let iterated: Vec<ExtractedString> = strings_iter(&config).map(|s| s.unwrap()).collect();
+        assert_eq!(expected, iterated);
+    }
+}
+
+#[test]
+fn test_null_terminated_only_threads() {
+    let mut file = NamedTempFile::new().unwrap();
+    let mut bytes = vec![];
+    for i in 0..64u8 {
+        bytes.extend_from_slice(b"terminated\x00");
+        bytes.extend(encode_utf16("wide string", false));
+        bytes.push(i % 2);
+    }
+    file.write_all(&bytes).unwrap();
+    let config = FileConfig::new(file.path())
+        .with_encodings(vec![Encoding::ASCII, Encoding::UTF16LE])
+        .with_null_terminated_only(true);
+    let expected = extract_strings(&config).unwrap();
+    assert!(expected.iter().all(|s| s.null_terminated == Some(true)));
+    for buffer_size in [1, 3, 16, 100] {
+        let config = FileConfig::new(file.path())
+            .with_encodings(vec![Encoding::ASCII, Encoding::UTF16LE])
+            .with_null_terminated_only(true)
+            .with_buffer_size(buffer_size)
+            .with_threads(4);
+        assert_eq!(expected, extract_strings(&config).unwrap());
+    }
+}
+
+fn elf_section_header(
+    name: u32,
+    section_type: u32,
+    flags: u64,
+    address: u64,
+    offset: u64,
+    size: u64,
+) -> Vec<u8> {
+    let mut header = vec![];
+    header.extend(name.to_le_bytes());
+    header.extend(section_type.to_le_bytes());
+    header.extend(flags.to_le_bytes());
+    header.extend(address.to_le_bytes());
+    header.extend(offset.to_le_bytes());
+    header.extend(size.to_le_bytes());
+    header.extend([0; 24]);
+    header
+}
+
+/// 64-bit little endian ELF executable made of `(name, address, contents)` loaded sections.
+fn elf_file(sections: &[(&str, u64, &[u8])]) -> Vec<u8> {
+    let mut names = b"\x00.shstrtab\x00".to_vec();
+    let mut contents = vec![];
+    let mut headers = vec![0; 64];
+    for (name, address, section_contents) in sections {
+        headers.extend(elf_section_header(
+            names.len() as u32,
+            1, // SHT_PROGBITS
+            2, // SHF_ALLOC
+            *address,
+            64 + contents.len() as u64,
+            section_contents.len() as u64,
+        ));
+        names.extend(name.bytes().chain([0]));
+        contents.extend(*section_contents);
+    }
+    let names_offset = 64 + contents.len() as u64;
+    headers.extend(elf_section_header(
+        1,
+        3,
+        0,
+        0,
+        names_offset,
+        names.len() as u64,
+    ));
+    contents.extend(names);
+    contents.resize(contents.len().next_multiple_of(8), 0);
+
+    let mut elf = b"\x7fELF\x02\x01\x01".to_vec();
+    elf.resize(16, 0);
+    elf.extend(2u16.to_le_bytes()); // ET_EXEC
+    elf.extend(62u16.to_le_bytes()); // EM_X86_64
+    elf.extend(1u32.to_le_bytes());
+    elf.extend(0u64.to_le_bytes());
+    elf.extend(0u64.to_le_bytes());
+    elf.extend((64 + contents.len() as u64).to_le_bytes());
+    elf.extend(0u32.to_le_bytes());
+    elf.extend([64, 0, 56, 0, 0, 0, 64, 0]);
+    elf.extend((sections.len() as u16 + 2).to_le_bytes());
+    elf.extend((sections.len() as u16 + 1).to_le_bytes());
+    elf.extend(contents);
+    elf.extend(headers);
+    elf
+}
+
+#[test]
+fn test_elf_sections() {
+    let elf = elf_file(&[
+        (".rodata", 0x400000, b"\x00rodata string\x00"),
+        (".data", 0x600000, b"data string\x00"),
+    ]);
+    let config = BytesConfig::new(elf.clone()).with_binary_format(BinaryFormat::Elf);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!(
+        vec![
+            ("rodata string", ".rodata", Some(0x400001), 65),
+            ("data string", ".data", Some(0x600000), 79),
+            (".shstrtab", ".shstrtab", None, 92),
+            (".rodata", ".shstrtab", None, 102),
+            (".data", ".shstrtab", None, 110),
+        ],
+        extracted
+            .iter()
+            .map(|s| (
+                s.text.as_str(),
+                s.section.as_deref().unwrap(),
+                s.virtual_address,
+                s.offset
+            ))
+            .collect::<Vec<_>>()
+    );
+    // The header isn't part of any section
+    assert!(strings(&BytesConfig::new(elf.clone()))
+        .unwrap()
+        .contains(&(String::from("ELF"), 1)));
+
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(&elf).unwrap();
+    let config = FileConfig::new(file.path())
+        .with_binary_format(BinaryFormat::Elf)
+        .with_sections(vec![String::from(".data")]);
+    assert_eq!(
+        vec![(String::from("data string"), 79)],
+        strings(&config).unwrap()
+    );
+    let extracted: Vec<ExtractedString> = strings_iter(&config).map(|s| s.unwrap()).collect();
+    assert_eq!(Some(String::from(".data")), extracted[0].section);
+    assert_eq!(Some(0x600000), extracted[0].virtual_address);
+}
+
+#[test]
+fn test_elf_sections_range() {
+    let elf = elf_file(&[(".rodata", 0x1000, b"first\x00second\x00")]);
+    let config = BytesConfig::new(elf)
+        .with_binary_format(BinaryFormat::Elf)
+        .with_sections(vec![String::from(".rodata")])
+        .with_start_offset(66);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!(
+        vec![
+            (String::from("rst"), 66, 0x1002),
+            (String::from("second"), 70, 0x1006)
+        ],
+        extracted
+            .into_iter()
+            .map(|s| (s.text, s.offset, s.virtual_address.unwrap()))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        extract_strings(&config).unwrap(),
+        extract_strings(&config.with_threads(2)).unwrap()
+    );
+}
+
+fn elf_program_header(
+    segment_type: u32,
+    offset: u64,
+    address: u64,
+    size: u64,
+    memory_size: u64,
+) -> Vec<u8> {
+    let mut header = vec![];
+    header.extend(segment_type.to_le_bytes());
+    header.extend(4u32.to_le_bytes()); // PF_R
+    header.extend(offset.to_le_bytes());
+    header.extend(address.to_le_bytes());
+    header.extend(0u64.to_le_bytes());
+    header.extend(size.to_le_bytes());
+    header.extend(memory_size.to_le_bytes());
+    header.extend(4u64.to_le_bytes());
+    header
+}
+
+/// 64-bit little endian ELF core dump made of `(address, contents, is_dumped)` loaded segments
+/// and an `NT_FILE` note of `(start, end, path)` mapped files.
+fn core_file(segments: &[(u64, &[u8], bool)], files: &[(u64, u64, &str)]) -> Vec<u8> {
+    let mut desc = vec![];
+    desc.extend((files.len() as u64).to_le_bytes());
+    desc.extend(4096u64.to_le_bytes());
+    for (start, end, _) in files {
+        desc.extend(start.to_le_bytes());
+        desc.extend(end.to_le_bytes());
+        desc.extend(0u64.to_le_bytes());
+    }
+    for (_, _, path) in files {
+        desc.extend(path.bytes().chain([0]));
+    }
+    desc.resize(desc.len().next_multiple_of(4), 0);
+    let mut note = vec![];
+    note.extend(5u32.to_le_bytes());
+    note.extend((desc.len() as u32).to_le_bytes());
+    note.extend(0x4649_4c45u32.to_le_bytes()); // NT_FILE
+    note.extend(b"CORE\x00\x00\x00\x00");
+    note.extend(desc);
+
+    let mut offset = 64 + 56 * (segments.len() as u64 + 1);
+    let mut headers = elf_program_header(4, offset, 0, note.len() as u64, 0); // PT_NOTE
+    let mut contents = note;
+    offset += contents.len() as u64;
+    for (address, segment_contents, is_dumped) in segments {
+        let size = segment_contents.len() as u64;
+        let file_size = if *is_dumped { size } else { 0 };
+        headers.extend(elf_program_header(1, offset, *address, file_size, size)); // PT_LOAD
+        if *is_dumped {
+            contents.extend(*segment_contents);
+            offset += size;
+        }
+    }
+
+    let mut elf = b"\x7fELF\x02\x01\x01".to_vec();
+    elf.resize(16, 0);
+    elf.extend(4u16.to_le_bytes()); // ET_CORE
+    elf.extend(62u16.to_le_bytes()); // EM_X86_64
+    elf.extend(1u32.to_le_bytes());
+    elf.extend(0u64.to_le_bytes());
+    elf.extend(64u64.to_le_bytes());
+    elf.extend(0u64.to_le_bytes());
+    elf.extend(0u32.to_le_bytes());
+    elf.extend([64, 0, 56, 0]);
+    elf.extend((segments.len() as u16 + 1).to_le_bytes());
+    elf.extend([64, 0, 0, 0, 0, 0]);
+    elf.extend(headers);
+    elf.extend(contents);
+    elf
+}
+
+#[test]
+fn test_core_dump() {
+    let mostly_zero = [vec![0; 4096], b"late string\x00".to_vec()].concat();
+    let core = core_file(
+        &[
+            (0x400000, b"\x00library string\x00", true),
+            (0x500000, b"not dumped string\x00", false),
+            // Dumped zero-filled memory is skipped, memory with a non-zero byte is scanned
+            (0x600000, &[0; 4096], true),
+            (0x680000, &mostly_zero, true),
+            (0x7ff000, b"heap string\x00", true),
+        ],
+        &[(0x400000, 0x401000, "/usr/lib/libexample.so")],
+    );
+    let config = BytesConfig::new(core.clone()).with_binary_format(BinaryFormat::Core);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!(
+        vec![
+            (
+                "library string",
+                Some(0x400001),
+                Some("/usr/lib/libexample.so")
+            ),
+            ("late string", Some(0x681000), None),
+            ("heap string", Some(0x7ff000), None),
+        ],
+        extracted
+            .iter()
+            .map(|s| (s.text.as_str(), s.virtual_address, s.mapping.as_deref()))
+            .collect::<Vec<_>>()
+    );
+    assert!(extracted.iter().all(|s| s.section.is_none()));
+    let iterated: Vec<ExtractedString> = strings_iter(&config).map(|s| s.unwrap()).collect();
+    assert_eq!(extracted, iterated);
+
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(&core).unwrap();
+    let config = FileConfig::new(file.path())
+        .with_binary_format(BinaryFormat::Core)
+        .with_buffer_size(64);
+    assert_eq!(extracted, extract_strings(&config).unwrap());
+    let config = config.with_sections(vec![String::from("/usr/lib/libexample.so")]);
+    assert_eq!(
+        vec![extracted[0].clone()],
+        extract_strings(&config).unwrap()
+    );
+
+    let elf = elf_file(&[(".rodata", 0x400000, b"rodata string\x00")]);
+    let config = BytesConfig::new(elf).with_binary_format(BinaryFormat::Core);
+    assert!(strings(&config).is_err());
+    assert_eq!(BinaryFormat::Core, BinaryFormat::from_str("core").unwrap());
+}
+
+#[test]
+fn test_elf_invalid() {
+    let config = BytesConfig::new(b"not an elf".to_vec()).with_binary_format(BinaryFormat::Elf);
+    assert!(strings(&config).is_err());
+    assert!(strings_iter(&config).next().unwrap().is_err());
+}
+
+fn utf16(text: &str) -> Vec<u8> {
+    text.encode_utf16()
+        .flat_map(|unit| unit.to_le_bytes())
+        .collect()
+}
+
+/// Resource directory of `(type, name, language, data)` resources, for a section at `rva`.
+fn pe_resource_section(rva: u32, resources: &[(u16, u16, u16, Vec<u8>)]) -> Vec<u8> {
+    let table = |entries: &[(u32, u32)]| {
+        let mut table = vec![0; 14];
+        table.extend((entries.len() as u16).to_le_bytes());
+        for (id, offset) in entries {
+            table.extend(id.to_le_bytes());
+            table.extend(offset.to_le_bytes());
+        }
+        table
+    };
+    let subdirectory = 0x8000_0000;
+    let types_length = 16 + 8 * resources.len() as u32;
+    let mut section = table(
+        &resources
+            .iter()
+            .enumerate()
+            .map(|(i, (type_id, _, _, _))| {
+                (
+                    u32::from(*type_id),
+                    subdirectory | (types_length + 64 * i as u32),
+                )
+            })
+            .collect::<Vec<_>>(),
+    );
+    let mut data_offset = types_length + 64 * resources.len() as u32;
+    for (i, (_, name, language, data)) in resources.iter().enumerate() {
+        let name_table = types_length + 64 * i as u32;
+        section.extend(table(&[(
+            u32::from(*name),
+            subdirectory | (name_table + 24),
+        )]));
+        section.extend(table(&[(u32::from(*language), name_table + 48)]));
+        section.extend((rva + data_offset).to_le_bytes());
+        section.extend((data.len() as u32).to_le_bytes());
+        section.extend([0; 8]);
+        data_offset += data.len().next_multiple_of(4) as u32;
+    }
+    for (_, _, _, data) in resources {
+        section.extend(data);
+        section.resize(section.len().next_multiple_of(4), 0);
+    }
+    section
+}
+
+/// A `VS_VERSIONINFO` block, `value_type` is 1 for text values.
+fn version_block(key: &str, value_type: u16, value: &[u8], children: &[Vec<u8>]) -> Vec<u8> {
+    let mut block = vec![0; 2];
+    let value_length = match value_type {
+        1 => value.len() / 2,
+        _ => value.len(),
+    };
+    block.extend((value_length as u16).to_le_bytes());
+    block.extend(value_type.to_le_bytes());
+    block.extend(utf16(key));
+    block.extend([0, 0]);
+    block.resize(block.len().next_multiple_of(4), 0);
+    block.extend(value);
+    for child in children {
+        block.resize(block.len().next_multiple_of(4), 0);
+        block.extend(child);
+    }
+    let length = (block.len() as u16).to_le_bytes();
+    block[..2].copy_from_slice(&length);
+    block
+}
+
+/// 64-bit PE made of `(name, rva, contents)` sections, a `.rsrc` section is the resource
+/// directory. Sections are 0x200 aligned in the file.
+fn pe_file(sections: &[(&str, u32, Vec<u8>)]) -> Vec<u8> {
+    let headers_length = (64 + 4 + 20 + 240 + 40 * sections.len()).next_multiple_of(0x200);
+    let mut pe = b"MZ".to_vec();
+    pe.resize(0x3c, 0);
+    pe.extend(64u32.to_le_bytes());
+    pe.extend(b"PE\x00\x00");
+    pe.extend(0x8664u16.to_le_bytes());
+    pe.extend((sections.len() as u16).to_le_bytes());
+    pe.extend([0; 12]);
+    pe.extend(240u16.to_le_bytes());
+    pe.extend(0x22u16.to_le_bytes());
+
+    let mut optional_header = vec![0; 240];
+    optional_header[..2].copy_from_slice(&0x20bu16.to_le_bytes());
+    optional_header[24..32].copy_from_slice(&0x140000000u64.to_le_bytes());
+    optional_header[32..36].copy_from_slice(&0x1000u32.to_le_bytes());
+    optional_header[36..40].copy_from_slice(&0x200u32.to_le_bytes());
+    optional_header[60..64].copy_from_slice(&(headers_length as u32).to_le_bytes());
+    optional_header[108..112].copy_from_slice(&16u32.to_le_bytes());
+    let mut contents = vec![];
+    let mut section_headers = vec![];
+    for (name, rva, section_contents) in sections {
+        let offset = headers_length + contents.len();
+        let mut section_name = name.as_bytes().to_vec();
+        section_name.resize(8, 0);
+        section_headers.extend(section_name);
+        section_headers.extend((section_contents.len() as u32).to_le_bytes());
+        section_headers.extend(rva.to_le_bytes());
+        section_headers.extend((section_contents.len() as u32).to_le_bytes());
+        section_headers.extend((offset as u32).to_le_bytes());
+        section_headers.extend([0; 12]);
+        section_headers.extend(0x40000040u32.to_le_bytes());
+        if *name == ".rsrc" {
+            optional_header[128..132].copy_from_slice(&rva.to_le_bytes());
+            optional_header[132..136]
+                .copy_from_slice(&(section_contents.len() as u32).to_le_bytes());
+        }
+        contents.extend(section_contents);
+        contents.resize(contents.len().next_multiple_of(0x200), 0);
+    }
+    pe.extend(optional_header);
+    pe.extend(section_headers);
+    pe.resize(headers_length, 0);
+    pe.extend(contents);
+    pe
+}
+
+#[test]
+fn test_pe_sections() {
+    let pe = pe_file(&[
+        (".text", 0x1000, b"\x00code string\x00".to_vec()),
+        (".rdata", 0x2000, b"\x00\x00data string\x00".to_vec()),
+    ]);
+    let config = BytesConfig::new(pe).with_binary_format(BinaryFormat::Pe);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!(
+        vec![
+            ("code string", ".text", 0x140001001, 0x1001, 0x201),
+            ("data string", ".rdata", 0x140002002, 0x2002, 0x402),
+        ],
+        extracted
+            .iter()
+            .map(|s| (
+                s.text.as_str(),
+                s.section.as_deref().unwrap(),
+                s.virtual_address.unwrap(),
+                s.relative_virtual_address.unwrap(),
+                s.offset
+            ))
+            .collect::<Vec<_>>()
+    );
+    let config = config.with_sections(vec![String::from(".rdata")]);
+    assert_eq!(
+        vec![(String::from("data string"), 0x402)],
+        strings(&config).unwrap()
+    );
+}
+
+#[test]
+fn test_pe_resources() {
+    let mut string_table = vec![];
+    for text in ["first string", "", "third"] {
+        string_table.extend((text.len() as u16).to_le_bytes());
+        string_table.extend(utf16(text));
+    }
+    string_table.extend([0; 26]);
+    let company_name = version_block("CompanyName", 1, &utf16("Test Company\0"), &[]);
+    let string_table_block = version_block("040904B0", 1, &[], &[company_name]);
+    let string_file_info = version_block("StringFileInfo", 1, &[], &[string_table_block]);
+    let version_info = version_block("VS_VERSION_INFO", 0, &[0; 52], &[string_file_info]);
+    let resources = pe_resource_section(
+        0x3000,
+        &[
+            (6, 7, 1033, string_table),
+            (16, 1, 1033, version_info),
+            (24, 1, 1033, b"<assembly/>".to_vec()),
+        ],
+    );
+    let pe = pe_file(&[(".rsrc", 0x3000, resources)]);
+    let config = BytesConfig::new(pe.clone())
+        .with_binary_format(BinaryFormat::Pe)
+        .with_min_length(4);
+    let extracted = extract_strings(&config).unwrap();
+    let resource_strings: Vec<(&str, &str, Encoding)> = extracted
+        .iter()
+        .filter_map(|s| Some((s.text.as_str(), s.resource.as_deref()?, s.encoding)))
+        .collect();
+    assert_eq!(
+        vec![
+            ("first string", "RT_STRING/7/1033/96", Encoding::UTF16LE),
+            ("third", "RT_STRING/7/1033/98", Encoding::UTF16LE),
+            (
+                "Test Company",
+                "RT_VERSION/1/1033/040904B0/CompanyName",
+                Encoding::UTF16LE
+            ),
+            ("<assembly/>", "RT_MANIFEST/1/1033", Encoding::ASCII),
+        ],
+        resource_strings
+    );
+    let company = &extracted[2];
+    assert_eq!(Some(true), company.null_terminated);
+    assert_eq!(
+        "Test Company",
+        String::from_utf16(
+            &pe[company.offset as usize..company.end_offset as usize]
+                .chunks(2)
+                .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                .collect::<Vec<_>>()
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        Some(company.offset - 0x200 + 0x3000),
+        company.relative_virtual_address
+    );
+    let iterated: Vec<ExtractedString> = strings_iter(&config).map(|s| s.unwrap()).collect();
+    assert_eq!(extracted, iterated);
+}
+
+/// 64-bit little endian Mach-O executable made of `(segment, section, address, contents)`
+/// sections, each one in its own segment.
+fn macho_file(cpu_type: u32, sections: &[(&str, &str, u64, Vec<u8>)]) -> Vec<u8> {
+    let commands_length = (72 + 80) * sections.len();
+    let mut commands = vec![];
+    let mut contents = vec![];
+    for (segment, section, address, section_contents) in sections {
+        let offset = (32 + commands_length + contents.len()) as u64;
+        let mut segment_name = segment.as_bytes().to_vec();
+        segment_name.resize(16, 0);
+        let mut section_name = section.as_bytes().to_vec();
+        section_name.resize(16, 0);
+        let length = section_contents.len() as u64;
+        commands.extend(0x19u32.to_le_bytes());
+        commands.extend(152u32.to_le_bytes());
+        commands.extend(&segment_name);
+        commands.extend(address.to_le_bytes());
+        commands.extend(length.to_le_bytes());
+        commands.extend(offset.to_le_bytes());
+        commands.extend(length.to_le_bytes());
+        commands.extend(5u32.to_le_bytes());
+        commands.extend(5u32.to_le_bytes());
+        commands.extend(1u32.to_le_bytes());
+        commands.extend(0u32.to_le_bytes());
+        commands.extend(section_name);
+        commands.extend(segment_name);
+        commands.extend(address.to_le_bytes());
+        commands.extend(length.to_le_bytes());
+        commands.extend((offset as u32).to_le_bytes());
+        commands.extend([0; 28]);
+        contents.extend(section_contents);
+        contents.resize(contents.len().next_multiple_of(8), 0);
+    }
+    let mut macho = 0xfeedfacfu32.to_le_bytes().to_vec();
+    macho.extend(cpu_type.to_le_bytes());
+    macho.extend(3u32.to_le_bytes());
+    macho.extend(2u32.to_le_bytes());
+    macho.extend((sections.len() as u32).to_le_bytes());
+    macho.extend((commands_length as u32).to_le_bytes());
+    macho.extend([0; 8]);
+    macho.extend(commands);
+    macho.extend(contents);
+    macho
+}
+
+/// Mach-O executable with a c string, an ascii CFString and a utf-16 CFString.
+fn macho_cfstring_file(cpu_type: u32) -> Vec<u8> {
+    let cfstring = |flags: u32, pointer: u64, length: u64| {
+        let mut cfstring = vec![0; 8];
+        cfstring.extend(flags.to_le_bytes());
+        cfstring.extend([0; 4]);
+        cfstring.extend(pointer.to_le_bytes());
+        cfstring.extend(length.to_le_bytes());
+        cfstring
+    };
+    let mut cfstrings = cfstring(0x7c8, 0x100001000 + 13, 7);
+    cfstrings.extend(cfstring(0x7d0, 0x100001800, 5));
+    macho_file(
+        cpu_type,
+        &[
+            (
+                "__TEXT",
+                "__cstring",
+                0x100001000,
+                b"\x00hello world\x00cf text\x00".to_vec(),
+            ),
+            ("__TEXT", "__ustring", 0x100001800, utf16("wide\u{e9}\0")),
+            ("__DATA", "__cfstring", 0x100002000, cfstrings),
+        ],
+    )
+}
+
+#[test]
+fn test_macho_cfstrings() {
+    let macho = macho_cfstring_file(0x01000007);
+    let config = BytesConfig::new(macho.clone())
+        .with_binary_format(BinaryFormat::MachO)
+        .with_encodings(vec![Encoding::ASCII, Encoding::UTF16LE]);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!(
+        vec![
+            ("hello world", "__TEXT,__cstring", None, Encoding::ASCII),
+            (
+                "cf text",
+                "__TEXT,__cstring",
+                Some(0x100002000),
+                Encoding::ASCII
+            ),
+            (
+                "wide\u{e9}",
+                "__TEXT,__ustring",
+                Some(0x100002020),
+                Encoding::UTF16LE
+            ),
+        ],
+        extracted
+            .iter()
+            .map(|s| (
+                s.text.as_str(),
+                s.section.as_deref().unwrap(),
+                s.cfstring_address,
+                s.encoding
+            ))
+            .collect::<Vec<_>>()
+    );
+    assert!(extracted
+        .iter()
+        .all(|s| s.architecture.as_deref() == Some("x86_64")));
+    let cf_text = &extracted[1];
+    assert_eq!(Some(true), cf_text.null_terminated);
+    assert_eq!(Some(0x100001000 + 13), cf_text.virtual_address);
+    assert_eq!(
+        b"cf text",
+        &macho[cf_text.offset as usize..cf_text.end_offset as usize]
+    );
+    let iterated: Vec<ExtractedString> = strings_iter(&config).map(|s| s.unwrap()).collect();
+    assert_eq!(extracted, iterated);
+    let config = config.with_sections(vec![String::from("__TEXT,__cstring")]);
+    assert_eq!(
+        vec!["hello world", "cf text"],
+        strings(&config)
+            .unwrap()
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_macho_cfstring_malformed_length() {
+    let cfstring = |flags: u32, pointer: u64, length: u64| {
+        let mut cfstring = vec![0; 8];
+        cfstring.extend(flags.to_le_bytes());
+        cfstring.extend([0; 4]);
+        cfstring.extend(pointer.to_le_bytes());
+        cfstring.extend(length.to_le_bytes());
+        cfstring
+    };
+    let mut cfstrings = cfstring(0x7d0, 0x100001000, 1 << 63);
+    cfstrings.extend(cfstring(0x7c8, 0x100001000, u64::MAX));
+    cfstrings.extend(cfstring(0x7c8, 0x100001000, 5));
+    let macho = macho_file(
+        0x01000007,
+        &[
+            ("__TEXT", "__cstring", 0x100001000, b"hello\x00".to_vec()),
+            ("__DATA", "__cfstring", 0x100002000, cfstrings),
+        ],
+    );
+    let config = BytesConfig::new(macho).with_binary_format(BinaryFormat::MachO);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!(
+        vec![("hello", Some(0x100002040))],
+        extracted
+            .iter()
+            .map(|s| (s.text.as_str(), s.cfstring_address))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_macho_fat() {
+    let arches = [
+        (0x01000007u32, macho_cfstring_file(0x01000007)),
+        (0x0100000c, macho_cfstring_file(0x0100000c)),
+    ];
+    let mut fat = 0xcafebabeu32.to_be_bytes().to_vec();
+    fat.extend((arches.len() as u32).to_be_bytes());
+    for (i, (cpu_type, macho)) in arches.iter().enumerate() {
+        fat.extend(cpu_type.to_be_bytes());
+        fat.extend(3u32.to_be_bytes());
+        fat.extend((0x1000 * (i as u32 + 1)).to_be_bytes());
+        fat.extend((macho.len() as u32).to_be_bytes());
+        fat.extend(12u32.to_be_bytes());
+    }
+    for (_, macho) in arches.iter() {
+        fat.resize(fat.len().next_multiple_of(0x1000), 0);
+        fat.extend(macho);
+    }
+    let config = BytesConfig::new(fat.clone()).with_binary_format(BinaryFormat::MachO);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!(
+        vec![
+            ("hello world", "x86_64"),
+            ("cf text", "x86_64"),
+            ("wide\u{e9}", "x86_64"),
+            ("hello world", "arm64"),
+            ("cf text", "arm64"),
+            ("wide\u{e9}", "arm64"),
+        ],
+        extracted
+            .iter()
+            .map(|s| (s.text.as_str(), s.architecture.as_deref().unwrap()))
+            .collect::<Vec<_>>()
+    );
+    for extracted_string in extracted
+        .into_iter()
+        .filter(|s| s.encoding == Encoding::ASCII)
+    {
+        assert_eq!(
+            extracted_string.text.as_bytes(),
+            &fat[extracted_string.offset as usize..extracted_string.end_offset as usize]
+        );
+    }
+    let config = BytesConfig::new(b"not a mach-o".to_vec()).with_binary_format(BinaryFormat::MachO);
+    assert!(extract_strings(&config).is_err());
+}
+
+/// Directory with `one.txt`, `sub/two.bin` and `sub/deep/three.txt`.
+fn strings_directory() -> tempfile::TempDir {
+    let directory = tempdir().unwrap();
+    fs::create_dir_all(directory.path().join("sub/deep")).unwrap();
+    fs::write(directory.path().join("one.txt"), b"first\x00").unwrap();
+    fs::write(directory.path().join("sub/two.bin"), b"\x00second\x00").unwrap();
+    fs::write(directory.path().join("sub/deep/three.txt"), b"third\x00").unwrap();
+    directory
+}
+
+fn file_strings(extracted: &[ExtractedString], directory: &Path) -> Vec<(String, String, u64)> {
+    extracted
+        .iter()
+        .map(|s| {
+            let file_name = PathBuf::from(s.file_name.as_ref().unwrap());
+            let relative_path = file_name.strip_prefix(directory).unwrap();
+            (
+                relative_path.to_string_lossy().replace('\\', "/"),
+                s.text.clone(),
+                s.offset,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_directory() {
+    let directory = strings_directory();
+    let path = directory.path();
+    let config = DirectoryConfig::new(vec![path.to_path_buf()]);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!(
+        vec![(String::from("one.txt"), String::from("first"), 0)],
+        file_strings(&extracted, path)
+    );
+    let config = config.with_recursive(true);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!(
+        vec![
+            (String::from("one.txt"), String::from("first"), 0),
+            (String::from("sub/deep/three.txt"), String::from("third"), 0),
+            (String::from("sub/two.bin"), String::from("second"), 1),
+        ],
+        file_strings(&extracted, path)
+    );
+    let iterated: Vec<ExtractedString> = strings_iter(&config).map(|s| s.unwrap()).collect();
+    assert_eq!(extracted, iterated);
+    let config = config.with_include(vec![String::from("*.txt")]);
+    assert_eq!(
+        vec!["first", "third"],
+        strings(&config)
+            .unwrap()
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect::<Vec<_>>()
+    );
+    let config = config.with_exclude(vec![String::from("**/deep")]);
+    assert_eq!(vec![(String::from("first"), 0)], strings(&config).unwrap());
+}
+
+/// Collects strings and the names of the files that couldn't be scanned.
+#[derive(Default)]
+struct ErrorsWriter {
+    texts: Vec<String>,
+    file_names: Vec<String>,
+}
+
+impl StringWriter for ErrorsWriter {
+    fn write_string(&mut self, extracted_string: ExtractedString) -> Result<(), Box<dyn Error>> {
+        self.texts.push(extracted_string.text);
+        Ok(())
+    }
+
+    fn write_error(&mut self, file_error: FileError) -> Result<(), Box<dyn Error>> {
+        self.file_names.push(file_error.file_name().to_owned());
+        Ok(())
+    }
+}
+
+#[test]
+fn test_directory_file_errors() {
+    let directory = strings_directory();
+    let missing_path = directory.path().join("missing");
+    let config = DirectoryConfig::new(vec![
+        missing_path.clone(),
+        directory.path().join("one.txt"),
+        directory.path().join("sub"),
+    ])
+    .with_binary_format(BinaryFormat::Elf);
+    let mut writer = ErrorsWriter::default();
+    extract_into(&config, &mut writer).unwrap();
+    assert!(writer.texts.is_empty());
+    assert_eq!(3, writer.file_names.len());
+    assert_eq!(missing_path.to_string_lossy(), writer.file_names[0]);
+
+    let config = config.with_binary_format(BinaryFormat::Raw);
+    let mut writer = ErrorsWriter::default();
+    extract_into(&config, &mut writer).unwrap();
+    assert_eq!(vec!["first", "second"], writer.texts);
+    assert_eq!(vec![missing_path.to_string_lossy()], writer.file_names);
+    let results: Vec<Result<ExtractedString, Box<dyn Error>>> = strings_iter(&config).collect();
+    assert_eq!(3, results.len());
+    let file_error = results[0].as_ref().unwrap_err();
+    assert_eq!(
+        missing_path.to_string_lossy(),
+        file_error.downcast_ref::<FileError>().unwrap().file_name()
+    );
+    assert_eq!(2, extract_strings(&config).unwrap().len());
+
+    let config = config.with_include(vec![String::from("[")]);
+    assert!(extract_strings(&config).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_directory_symlinks() {
+    let directory = strings_directory();
+    let path = directory.path();
+    std::os::unix::fs::symlink(path.join("one.txt"), path.join("sub/link.txt")).unwrap();
+    let config = DirectoryConfig::new(vec![path.join("sub")]);
+    assert_eq!(vec![(String::from("second"), 1)], strings(&config).unwrap());
+    let config = config.with_follow_symlinks(true);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!(
+        vec![
+            (String::from("sub/link.txt"), String::from("first"), 0),
+            (String::from("sub/two.bin"), String::from("second"), 1),
+        ],
+        file_strings(&extracted, path)
+    );
+}
+
+/// Tar archive of `(path, contents)` files.
+fn tar_archive(files: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut builder = tar::Builder::new(vec![]);
+    for (path, contents) in files {
+        let mut header = tar::Header::new_ustar();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        builder.append_data(&mut header, path, *contents).unwrap();
+    }
+    builder.into_inner().unwrap()
+}
+
+/// Zip archive of `(path, contents)` files, compressed with deflate when it is enabled.
+fn zip_archive(files: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(vec![]));
+    #[cfg(feature = "gzip")]
+    let method = zip::CompressionMethod::Deflated;
+    #[cfg(not(feature = "gzip"))]
+    let method = zip::CompressionMethod::Stored;
+    let options = zip::write::SimpleFileOptions::default().compression_method(method);
+    for (path, contents) in files {
+        writer.start_file(*path, options).unwrap();
+        writer.write_all(contents).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+fn member_strings(extracted: &[ExtractedString]) -> Vec<(&str, &str, u64)> {
+    extracted
+        .iter()
+        .map(|s| (s.member.as_deref().unwrap(), s.text.as_str(), s.offset))
+        .collect()
+}
+
+#[test]
+fn test_archives() {
+    let tar = tar_archive(&[("a.txt", b"first"), ("dir/b.bin", b"\x00\x00second\x00")]);
+    let zip = zip_archive(&[("inner.tar", &tar), ("c.txt", b"third third third")]);
+    let config = BytesConfig::new(zip.clone()).with_archives(true);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!(
+        vec![
+            ("inner.tar/a.txt", "first", 0),
+            ("inner.tar/dir/b.bin", "second", 2),
+            ("c.txt", "third third third", 0),
+        ],
+        member_strings(&extracted)
+    );
+    let iterated: Vec<ExtractedString> = strings_iter(&config).map(|s| s.unwrap()).collect();
+    assert_eq!(extracted, iterated);
+
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(&zip).unwrap();
+    let config = FileConfig::new(file.path()).with_archives(true);
+    assert_eq!(extracted, extract_strings(&config).unwrap());
+
+    let config = BytesConfig::new(zip).with_archives(false);
+    assert!(extract_strings(&config)
+        .unwrap()
+        .iter()
+        .all(|s| s.member.is_none()));
+}
+
+#[test]
+fn test_archive_limits() {
+    let tar = tar_archive(&[("a.txt", b"first"), ("big.txt", &[b'x'; 1000])]);
+    let zip = zip_archive(&[("inner.tar", &tar)]);
+    let config = BytesConfig::new(zip.clone())
+        .with_archives(true)
+        .with_max_member_size(4096);
+    let mut writer = ErrorsWriter::default();
+    extract_into(&config, &mut writer).unwrap();
+    assert_eq!(vec!["first", &"x".repeat(1000)], writer.texts);
+    assert!(writer.file_names.is_empty());
+
+    let config = config.with_max_member_size(512);
+    let mut writer = ErrorsWriter::default();
+    extract_into(&config, &mut writer).unwrap();
+    assert_eq!(vec!["inner.tar"], writer.file_names);
+    let results: Vec<Result<ExtractedString, Box<dyn Error>>> = strings_iter(&config).collect();
+    let file_error = results[0].as_ref().unwrap_err();
+    assert_eq!(
+        "inner.tar",
+        file_error.downcast_ref::<FileError>().unwrap().file_name()
+    );
+
+    let config = BytesConfig::new(zip.clone())
+        .with_archives(true)
+        .with_max_member_size(tar.len() as u64)
+        .with_max_archive_depth(1);
+    let extracted = extract_strings(&config).unwrap();
+    assert!(extracted
+        .iter()
+        .all(|s| s.member.as_deref() == Some("inner.tar")));
+    assert!(extracted.iter().any(|s| s.text == "ustar"));
+
+    let config = config.with_max_member_size(tar.len() as u64 - 1);
+    assert!(extract_strings(&config).unwrap().is_empty());
+}
+
+#[test]
+fn test_directory_archives() {
+    let directory = tempdir().unwrap();
+    let zip = zip_archive(&[("member.txt", b"zipped"), ("broken.tar", b"not a tar")]);
+    fs::write(directory.path().join("archive.zip"), zip).unwrap();
+    let tar = tar_archive(&[("ok.txt", b"tarred"), ("big.txt", &[b'y'; 100])]);
+    fs::write(directory.path().join("archive.tar"), tar).unwrap();
+    let config = DirectoryConfig::new(vec![directory.path().to_path_buf()])
+        .with_archives(true)
+        .with_max_member_size(50);
+    let mut writer = ErrorsWriter::default();
+    extract_into(&config, &mut writer).unwrap();
+    assert_eq!(vec!["tarred", "zipped", "not a tar"], writer.texts);
+    let extracted = extract_strings(&config).unwrap();
+    let relative_names: Vec<(String, &str)> = file_strings(&extracted, directory.path())
+        .into_iter()
+        .zip(&extracted)
+        .map(|((file_name, _, _), s)| (file_name, s.member.as_deref().unwrap()))
+        .collect();
+    assert_eq!(
+        vec![
+            (String::from("archive.tar"), "ok.txt"),
+            (String::from("archive.zip"), "member.txt"),
+            (String::from("archive.zip"), "broken.tar"),
+        ],
+        relative_names
+    );
+    let iterated: Vec<ExtractedString> = strings_iter(&config).filter_map(|s| s.ok()).collect();
+    assert_eq!(extracted, iterated);
+    let file_name = directory.path().join("archive.tar/big.txt");
+    assert_eq!(vec![file_name.to_string_lossy()], writer.file_names);
+    let errors: Vec<String> = strings_iter(&config)
+        .filter_map(|s| s.err())
+        .map(|err| err.downcast::<FileError>().unwrap().file_name().to_owned())
+        .collect();
+    assert_eq!(writer.file_names, errors);
+}
+
+#[cfg(feature = "gzip")]
+fn gzip(contents: &[u8]) -> Vec<u8> {
+    let mut encoder = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+    encoder.write_all(contents).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn test_decompression_uncompressed() {
+    let config = BytesConfig::new(b"\x00\x00first string\x00".to_vec())
+        .with_decompression(Decompression::Auto);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!("first string", extracted[0].text);
+    assert_eq!(2, extracted[0].offset);
+    assert_eq!(None, extracted[0].compression);
+    assert_eq!("Zstd", Compression::Zstd.to_string());
+}
+
+#[cfg(any(feature = "gzip", feature = "xz", feature = "zstd", feature = "bzip2"))]
+const COMPRESSED_STRINGS: &[u8] = b"\x00\x00first string\x00second string\x00";
+
+/// Checks that `compressed`, the compression of `COMPRESSED_STRINGS`, is scanned decompressed.
+#[cfg(any(feature = "gzip", feature = "xz", feature = "zstd", feature = "bzip2"))]
+fn check_decompression(compressed: Vec<u8>, compression: Compression) {
+    let config = BytesConfig::new(compressed.clone()).with_decompression(Decompression::Auto);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!(
+        vec![
+            (String::from("first string"), 2),
+            (String::from("second string"), 15)
+        ],
+        strings(&config).unwrap()
+    );
+    assert!(extracted.iter().all(|s| s.compression == Some(compression)));
+    let iterated: Vec<ExtractedString> = strings_iter(&config).map(|s| s.unwrap()).collect();
+    assert_eq!(extracted, iterated);
+
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(&compressed).unwrap();
+    let config = FileConfig::new(file.path()).with_decompression(Decompression::Auto);
+    assert_eq!(extracted, extract_strings(&config).unwrap());
+    let config = config.with_range(15, 100).with_threads(2);
+    assert_eq!(
+        vec![(String::from("second string"), 15)],
+        strings(&config).unwrap()
+    );
+
+    let config = BytesConfig::new(compressed);
+    assert!(!strings(&config)
+        .unwrap()
+        .contains(&(String::from("first string"), 2)));
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_gzip_decompression() {
+    check_decompression(gzip(COMPRESSED_STRINGS), Compression::Gzip);
+    // Streams of a multi-member file are read one after the other
+    let mut compressed = gzip(b"\x00\x00first ");
+    compressed.extend(gzip(b"string\x00second string\x00"));
+    check_decompression(compressed, Compression::Gzip);
+}
+
+#[cfg(feature = "xz")]
+#[test]
+fn test_xz_decompression() {
+    let mut encoder = xz2::write::XzEncoder::new(vec![], 6);
+    encoder.write_all(COMPRESSED_STRINGS).unwrap();
+    check_decompression(encoder.finish().unwrap(), Compression::Xz);
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn test_zstd_decompression() {
+    let compressed = zstd::encode_all(COMPRESSED_STRINGS, 0).unwrap();
+    check_decompression(compressed, Compression::Zstd);
+}
+
+#[cfg(feature = "bzip2")]
+#[test]
+fn test_bzip2_decompression() {
+    let mut encoder = bzip2::write::BzEncoder::new(vec![], bzip2::Compression::default());
+    encoder.write_all(COMPRESSED_STRINGS).unwrap();
+    check_decompression(encoder.finish().unwrap(), Compression::Bzip2);
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_decompression_layers() {
+    let tar = tar_archive(&[("a.txt", b"first"), ("b.txt.gz", &gzip(b"second"))]);
+    let config = BytesConfig::new(gzip(&tar))
+        .with_archives(true)
+        .with_decompression(Decompression::Auto);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!(
+        vec![("a.txt", "first", 0), ("b.txt.gz", "second", 0)],
+        member_strings(&extracted)
+    );
+    assert!(extracted
+        .iter()
+        .all(|s| s.compression == Some(Compression::Gzip)));
+    let iterated: Vec<ExtractedString> = strings_iter(&config).map(|s| s.unwrap()).collect();
+    assert_eq!(extracted, iterated);
+
+    // A zip is read from its end and then from every member, the stream is decompressed once
+    let zip = zip_archive(&[("a.txt", b"first"), ("b.txt", b"second")]);
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(&gzip(&zip)).unwrap();
+    let config = FileConfig::new(file.path())
+        .with_archives(true)
+        .with_decompression(Decompression::Auto);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!(
+        vec![("a.txt", "first", 0), ("b.txt", "second", 0)],
+        member_strings(&extracted)
+    );
+    assert!(extracted
+        .iter()
+        .all(|s| s.compression == Some(Compression::Gzip) && s.file_name.is_none()));
+    let iterated: Vec<ExtractedString> = strings_iter(&config).map(|s| s.unwrap()).collect();
+    assert_eq!(extracted, iterated);
+
+    let elf = elf_file(&[(".rodata", 0x400000, b"\x00rodata string\x00")]);
+    let config = BytesConfig::new(gzip(&elf))
+        .with_binary_format(BinaryFormat::Elf)
+        .with_sections(vec![String::from(".rodata")])
+        .with_decompression(Decompression::Auto);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!("rodata string", extracted[0].text);
+    assert_eq!(Some(0x400001), extracted[0].virtual_address);
+    assert_eq!(Some(Compression::Gzip), extracted[0].compression);
+
+    let directory = tempdir().unwrap();
+    fs::write(directory.path().join("log.gz"), gzip(b"compressed")).unwrap();
+    fs::write(directory.path().join("log.txt"), b"plain").unwrap();
+    let config = DirectoryConfig::new(vec![directory.path().to_path_buf()])
+        .with_decompression(Decompression::Auto);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!(
+        vec![("compressed", Some(Compression::Gzip)), ("plain", None)],
+        extracted
+            .iter()
+            .map(|s| (s.text.as_str(), s.compression))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[cfg(target_os = "linux")]
+static PROCESS_STRING: &[u8] = b"\x00string in the memory of the process\x00";
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_process() {
+    let address = PROCESS_STRING.as_ptr() as u64;
+    let heap_string = b"\x00string on the heap\x00".to_vec();
+    let heap_address = heap_string.as_ptr() as u64;
+    let config = ProcessConfig::new(std::process::id()).with_range(address, 64);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!("string in the memory of the process", extracted[0].text);
+    assert_eq!(address + 1, extracted[0].offset);
+    assert_eq!(Some(address + 1), extracted[0].virtual_address);
+    let executable = std::env::current_exe().unwrap();
+    assert_eq!(
+        Some(executable.to_string_lossy().into_owned()),
+        extracted[0].mapping
+    );
+    let iterated: Vec<ExtractedString> = strings_iter(&config).map(|s| s.unwrap()).collect();
+    assert_eq!(extracted, iterated);
+
+    let config = ProcessConfig::new(std::process::id()).with_range(heap_address, 20);
+    assert_eq!(
+        vec![(String::from("string on the heap"), heap_address + 1)],
+        strings(&config).unwrap()
+    );
+
+    let config = ProcessConfig::new(u32::MAX);
+    assert!(extract_strings(&config).is_err());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_process_unreadable_page() {
+    // The page of a file mapping past the end of the file can't be read
+    let mut file = NamedTempFile::new().unwrap();
+    let mut contents = vec![0; 4096 - 18];
+    contents.extend_from_slice(b"string before page");
+    file.write_all(&contents).unwrap();
+    let mmap = unsafe {
+        memmap2::MmapOptions::new()
+            .len(8192)
+            .map(file.as_file())
+            .unwrap()
+    };
+    let address = mmap.as_ptr() as u64;
+    let config = ProcessConfig::new(std::process::id()).with_range(address, 8192);
+    let extracted = extract_strings(&config).unwrap();
+    assert_eq!(
+        vec![("string before page", address + 4078, None)],
+        extracted
+            .iter()
+            .map(|s| (s.text.as_str(), s.offset, s.null_terminated))
+            .collect::<Vec<_>>()
+    );
+    let config = config.with_null_terminated_only(true);
+    assert!(strings(&config).unwrap().is_empty());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_process_all_mappings() {
+    // Mappings such as `[vvar]` are readable in the maps but not through `/proc/<pid>/mem`
+    let config = ProcessConfig::new(std::process::id())
+        .with_min_length(30)
+        .with_threads(2);
+    let extracted = extract_strings(&config).unwrap();
+    assert!(extracted
+        .iter()
+        .any(|s| s.text == "string in the memory of the process"));
+    assert!(extracted
+        .iter()
+        .all(|s| Some(s.offset) == s.virtual_address));
+}
@@ -1,5 +1,6 @@
 import json
 import os
+import sys
 from pathlib import Path
 from uuid import uuid4
 
@@ -72,3 +73,110 @@ def test_json_dump_multiple_strings(temp_file: Path):
         ['test"\n\tmore', 2],
         ["more text over here", 15],
     ]
+
+
+def test_utf8():
+    extracted = rust_strings.strings(
+        bytes="\x00héllo wörld\x00".encode(), encodings=["utf-8"]
+    )
+    assert extracted == [("héllo wörld", 1)]
+
+
+def test_utf16le_unicode_scripts():
+    data = "Привет, мир".encode("utf-16le")
+    assert rust_strings.strings(bytes=data, encodings=["utf-16le"]) == []
+    extracted = rust_strings.strings(
+        bytes=data, encodings=["utf-16le"], unicode_scripts=["cyrillic"]
+    )
+    assert extracted == [("Привет, мир", 0)]
+
+
+def test_utf32():
+    extracted = rust_strings.strings(
+        bytes="\x00test\x00".encode("utf-32be"), encodings=["utf-32be"]
+    )
+    assert extracted == [("test", 4)]
+
+
+def test_extract_strings():
+    extracted = rust_strings.extract_strings(
+        bytes=b"test\x00\x01t\x00e\x00s\x00t\x00", encodings=["ascii", "utf-16le"]
+    )
+    assert [(s.text, s.offset, s.end_offset, s.encoding) for s in extracted] == [
+        ("test", 0, 4, "ASCII"),
+        ("test", 6, 14, "UTF16LE"),
+    ]
+    assert extracted[0].byte_length == 4
+    assert extracted[0].null_terminated is True
+    assert extracted[1].null_terminated is None
+
+
+def test_file_mmap(temp_file: Path):
+    temp_file.write_bytes(b"first\x00second")
+    extracted = rust_strings.strings(file_path=temp_file, mmap=True)
+    assert extracted == [("first", 0), ("second", 6)]
+
+
+def test_threads(temp_file: Path):
+    temp_file.write_bytes(b"first\x00second\x00" * 1000)
+    extracted = rust_strings.strings(file_path=temp_file, buffer_size=7, threads=4)
+    assert extracted == rust_strings.strings(file_path=temp_file)
+
+
+def test_range():
+    extracted = rust_strings.strings(bytes=b"first\x00second\x00third", start_offset=8, end_offset=16)
+    assert extracted == [("cond", 8), ("thi", 13)]
+
+
+def test_max_length():
+    data = b"0123456789\x00ab"
+    assert rust_strings.strings(bytes=data, max_length=4) == [("0123...", 0)]
+    assert rust_strings.strings(bytes=data, max_length=4, max_length_policy="drop") == []
+    extracted = rust_strings.extract_strings(bytes=data, max_length=4, max_length_policy="split")
+    assert [(s.text, s.offset, s.end_offset, s.null_terminated) for s in extracted] == [
+        ("0123", 0, 4, False),
+        ("4567", 4, 8, False),
+        ("89", 8, 10, True),
+    ]
+
+
+def test_null_terminated_only():
+    extracted = rust_strings.extract_strings(
+        bytes=b"first\x00second\x01third", null_terminated_only=True
+    )
+    assert [(s.text, s.offset, s.null_terminated) for s in extracted] == [("first", 0, True)]
+
+
+def test_max_length_policy_not_found():
+    with pytest.raises(rust_strings.MaxLengthPolicyNotFoundException):
+        rust_strings.strings(bytes=b"test", max_length=2, max_length_policy="unknown")
+
+
+@pytest.mark.skipif(not sys.platform.startswith("linux"), reason="needs an ELF executable")
+def test_elf_sections():
+    extracted = rust_strings.extract_strings(
+        file_path=sys.executable, binary_format="elf", sections=[".dynstr"]
+    )
+    assert extracted
+    assert all(s.section == ".dynstr" and s.virtual_address is not None for s in extracted)
+
+
+def test_binary_format_not_found():
+    with pytest.raises(rust_strings.BinaryFormatNotFoundException):
+        rust_strings.strings(bytes=b"test", binary_format="unknown")
+
+
+def test_pe_invalid():
+    with pytest.raises(rust_strings.StringsException):
+        rust_strings.extract_strings(bytes=b"MZ not a pe", binary_format="pe")
+
+
+@pytest.mark.skipif(not sys.platform.startswith("linux"), reason="needs an ELF executable")
+def test_core_invalid():
+    with pytest.raises(rust_strings.StringsException):
+        rust_strings.extract_strings(file_path=sys.executable, binary_format="core")
+
+
+def test_macho_invalid():
+    with pytest.raises(rust_strings.StringsException):
+        rust_strings.extract_strings(bytes=b"\xcf\xfa\xed\xfe truncated", binary_format="macho")